@@ -3,7 +3,16 @@
 //! This program generates a fixed sequence of pseudo-random frames (Linear
 //! Congruential Generator seeded with 0xDEADBEEF) and measures the time taken
 //! to (1) encode each frame using `encode_frame` and (2) decode the concatenated
-//! stream back into frames with `decode_frames`.
+//! stream back into frames with `decode_frames`, for random, ASCII-only and
+//! escape-heavy payloads (about half `END`/`ESC` bytes). It then streams a subset
+//! of the frames through `SlipReader` over a `Cursor`, with the default exact
+//! reads and with read-ahead, and with read-ahead over a reader that returns a
+//! single byte per `read` call, to show the effect of the reader's internal
+//! buffering. Finally it writes the same subset through `SlipWriter` and counts
+//! heap allocations with a wrapping global allocator, showing that the writer
+//! reuses its encode buffer instead of allocating per frame. A last run decodes
+//! a stream of 4-byte frames with both `decode_frames` and
+//! `decode_small_frames`, which stores tiny payloads inline.
 //!
 //! Notes:
 //! - The RNG uses a fixed seed so the benchmark is reproducible.
@@ -18,8 +27,34 @@
 //!
 //! To run the example with fewer frames for a quick smoke-test, edit
 //! `FRAME_COUNT` near the top of the example (or set it to e.g. 20_000).
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Instant;
 
+/// Global allocator that counts allocations so the writer benchmark can report them.
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
 fn main() -> slipspeed::Result<()> {
     // Default number of frames for the benchmark. You can override this for
     // quick smoke-tests by setting the BENCH_FRAMES environment variable,
@@ -27,6 +62,8 @@ fn main() -> slipspeed::Result<()> {
     const FRAME_COUNT: usize = 5_000_000;
     // Fixed frame length to exercise fixed-size frames as requested.
     const FRAME_LEN: usize = 128;
+    // Number of frames streamed through `SlipReader`.
+    const READER_FRAME_COUNT: usize = 500_000;
 
     // Allow overriding the frame count for quick local runs.
     let frame_count = std::env::var("BENCH_FRAMES")
@@ -43,9 +80,28 @@ fn main() -> slipspeed::Result<()> {
         0x20u8.wrapping_add(v)
     });
 
-    // Run the benchmark twice and report labeled results.
+    // Frames where about half of the bytes are END or ESC, the worst case for escape expansion.
+    let frames_escape_heavy = make_frames(frame_count, FRAME_LEN, 0xDEADBEEF, |rng: &mut Lcg| {
+        let v = rng.next_u8();
+        match v & 3 {
+            0 => slipspeed::END,
+            1 => slipspeed::ESC,
+            _ => v,
+        }
+    });
+
+    // Run the benchmark for each payload kind and report labeled results.
     run_bench("random bytes", &frames_random)?;
     run_bench("ASCII-only bytes", &frames_ascii)?;
+    run_bench("escape-heavy bytes", &frames_escape_heavy)?;
+
+    // The one-byte reader is slow by design, so only stream a subset through it.
+    let reader_frames = &frames_random[..frame_count.min(READER_FRAME_COUNT)];
+    run_reader_bench(reader_frames)?;
+    run_writer_bench(reader_frames)?;
+
+    let tiny_frames = make_frames(frame_count, 4, 0xDEADBEEF, |rng: &mut Lcg| rng.next_u8());
+    run_small_frames_bench(&tiny_frames)?;
 
     Ok(())
 }
@@ -103,6 +159,168 @@ fn run_bench(label: &str, frames: &[Vec<u8>]) -> slipspeed::Result<()> {
     Ok(())
 }
 
+fn run_reader_bench(frames: &[Vec<u8>]) -> slipspeed::Result<()> {
+    let encoded: Vec<u8> = frames
+        .iter()
+        .flat_map(|frame| slipspeed::encode_frame(frame))
+        .collect();
+
+    println!("--- Benchmark: SlipReader ({} frames) ---", frames.len());
+    let cursor = std::io::Cursor::new(encoded.as_slice());
+    let reader =
+        slipspeed::SlipReader::new(cursor).with_read_strategy(slipspeed::ReadStrategy::Exact);
+    let elapsed = read_all_frames(reader, frames.len())?;
+    println!(
+        "Cursor, exact reads: {:?} ({:.0} frames/s)",
+        elapsed,
+        frames_per_sec(elapsed, frames.len())
+    );
+    let cursor = std::io::Cursor::new(encoded.as_slice());
+    let reader =
+        slipspeed::SlipReader::new(cursor).with_read_strategy(slipspeed::ReadStrategy::Fixed(4096));
+    let elapsed = read_all_frames(reader, frames.len())?;
+    println!(
+        "Cursor, read-ahead: {:?} ({:.0} frames/s)",
+        elapsed,
+        frames_per_sec(elapsed, frames.len())
+    );
+    let stingy = OneByteReader {
+        data: &encoded,
+        pos: 0,
+    };
+    let reader =
+        slipspeed::SlipReader::new(stingy).with_read_strategy(slipspeed::ReadStrategy::Fixed(4096));
+    let elapsed = read_all_frames(reader, frames.len())?;
+    println!(
+        "1-byte reads: {:?} ({:.0} frames/s)",
+        elapsed,
+        frames_per_sec(elapsed, frames.len())
+    );
+    println!();
+
+    Ok(())
+}
+
+fn run_writer_bench(frames: &[Vec<u8>]) -> slipspeed::Result<()> {
+    // Pre-size the sink so only the writer's own allocations are counted.
+    let sink = Vec::with_capacity(slipspeed::total_encoded_len(frames));
+
+    println!("--- Benchmark: SlipWriter ({} frames) ---", frames.len());
+    let mut writer = slipspeed::SlipWriter::new(sink);
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for frame in frames {
+        writer.write_frame(frame)?;
+    }
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    println!(
+        "SlipWriter: {:?} ({:.0} frames/s), {} allocations",
+        elapsed,
+        frames_per_sec(elapsed, frames.len()),
+        allocations
+    );
+
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    for frame in frames {
+        std::hint::black_box(slipspeed::encode_frame(frame));
+    }
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    println!("encode_frame per frame: {} allocations", allocations);
+    println!();
+
+    Ok(())
+}
+
+fn run_small_frames_bench(frames: &[Vec<u8>]) -> slipspeed::Result<()> {
+    let encoded: Vec<u8> = frames
+        .iter()
+        .flat_map(|frame| slipspeed::encode_frame(frame))
+        .collect();
+
+    println!("--- Benchmark: 4-byte frames ({} frames) ---", frames.len());
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let decoded = slipspeed::decode_frames(&encoded)?;
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    assert_eq!(
+        decoded.len(),
+        frames.len(),
+        "decode_frames frame count mismatch"
+    );
+    println!(
+        "decode_frames: {:?} ({:.2} ns/frame), {} allocations",
+        elapsed,
+        ns_per_item(elapsed, frames.len()),
+        allocations
+    );
+    drop(decoded);
+
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let decoded = slipspeed::decode_small_frames::<16>(&encoded)?;
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    assert!(
+        decoded
+            .iter()
+            .zip(frames)
+            .all(|(small, frame)| small[..] == frame[..]),
+        "decode_small_frames mismatch"
+    );
+    println!(
+        "decode_small_frames::<16>: {:?} ({:.2} ns/frame), {} allocations",
+        elapsed,
+        ns_per_item(elapsed, frames.len()),
+        allocations
+    );
+    println!();
+
+    Ok(())
+}
+
+fn read_all_frames<R: std::io::Read>(
+    mut reader: slipspeed::SlipReader<R>,
+    expected: usize,
+) -> slipspeed::Result<std::time::Duration> {
+    let start = Instant::now();
+    let mut frame = Vec::new();
+    let mut count = 0usize;
+    while reader.read_frame_into(&mut frame)?.is_some() {
+        count += 1;
+    }
+    let elapsed = start.elapsed();
+    assert_eq!(count, expected, "SlipReader frame count mismatch");
+    Ok(elapsed)
+}
+
+fn frames_per_sec(duration: std::time::Duration, count: usize) -> f64 {
+    let secs = duration.as_secs_f64();
+    if secs > 0.0 {
+        count as f64 / secs
+    } else {
+        0.0
+    }
+}
+
+/// Reader that hands out at most one byte per `read` call, like a slow serial port.
+struct OneByteReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl std::io::Read for OneByteReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.pos == self.data.len() || buf.is_empty() {
+            return Ok(0);
+        }
+        buf[0] = self.data[self.pos];
+        self.pos += 1;
+        Ok(1)
+    }
+}
+
 struct Lcg {
     state: u64,
 }
@@ -0,0 +1,52 @@
+//! Stream-decode a (potentially huge) SLIP capture file through a memory map.
+//!
+//! Usage: `cargo run --release --example decode_file [path]`. Without a path, a synthetic
+//! capture is written to the system temp directory first.
+
+use memmap2::Mmap;
+use slipspeed::{encode_frame, FrameIter, Result};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+
+fn write_sample(path: &PathBuf) -> Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    for i in 0..100_000u32 {
+        out.write_all(&encode_frame(&i.to_le_bytes().repeat(16)))?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let path = match std::env::args_os().nth(1) {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let path = std::env::temp_dir().join("slipspeed-sample.slip");
+            write_sample(&path)?;
+            path
+        }
+    };
+
+    let file = File::open(&path)?;
+    // SAFETY: the file must not be truncated or modified while it is mapped.
+    let mmap = unsafe { Mmap::map(&file)? };
+    // Frames are decoded front to back, so hint the kernel to read ahead aggressively. This is
+    // only advice: pages already decoded stay cached until the kernel reclaims them, which it
+    // may do earlier for a sequential mapping.
+    #[cfg(unix)]
+    mmap.advise(memmap2::Advice::Sequential)?;
+
+    let mut frames = 0usize;
+    let mut payload_bytes = 0usize;
+    for frame in FrameIter::new(&mmap) {
+        let frame = frame?;
+        frames += 1;
+        payload_bytes += frame.len();
+    }
+    println!(
+        "{}: {frames} frames, {payload_bytes} payload bytes",
+        path.display()
+    );
+    Ok(())
+}
@@ -18,8 +18,10 @@ async fn main() -> slipspeed::Result<()> {
     let mut reader = Framed::new(server, SlipCodec::new());
 
     writer.send(b"hello".to_vec()).await?;
-    writer.send(b"world".to_vec()).await?;
-    writer.flush().await?;
+    // Borrowed slices are encoded without allocating a `Vec` first.
+    writer.send(&b"world"[..]).await?;
+    // The codec encodes several item types, so name the one whose sink should flush.
+    SinkExt::<&[u8]>::flush(&mut writer).await?;
 
     while let Some(frame) = reader.next().await.transpose()? {
         println!("Received frame: {:?}", String::from_utf8_lossy(&frame));
@@ -1,14 +1,46 @@
 use asynchronous_codec::{Decoder, Encoder};
-use bytes::BytesMut;
+use bytes::{Buf, Bytes, BytesMut};
+use futures_util::io::{AsyncRead, AsyncWrite};
+use futures_util::sink::Sink;
+use futures_util::stream::{FusedStream, Stream};
+use std::future::poll_fn;
 use std::io::{self, Write};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+#[cfg(feature = "idle-flush")]
+use std::time::Duration;
+#[cfg(feature = "idle-flush")]
+use tokio::time::Instant;
 
-use crate::{encode_into_writer, process_byte, DecoderState, Result, SlipError};
+use crate::{
+    check_frame_closed, encode_into_writer, encoded_frame_len, escape_runs, process_byte,
+    DecoderState, FrameSink, NoopObserver, Observer, Result, SlipError, DEFAULT_FLUSH_THRESHOLD,
+    END,
+};
 
 /// SLIP codec implementing the [`asynchronous_codec`] traits.
-#[derive(Default)]
-pub struct SlipCodec {
+///
+/// Encode and decode events are reported to an [`Observer`] installed with
+/// [`with_observer`](SlipCodec::with_observer).
+pub struct SlipCodec<O = NoopObserver> {
     state: DecoderState,
     buffer: Vec<u8>,
+    frame_open: bool,
+    /// Payload bytes passed to [`encode_partial`](SlipCodec::encode_partial) for the open frame.
+    open_len: usize,
+    observer: O,
+}
+
+impl Default for SlipCodec {
+    fn default() -> Self {
+        Self {
+            state: DecoderState::default(),
+            buffer: Vec::new(),
+            frame_open: false,
+            open_len: 0,
+            observer: NoopObserver,
+        }
+    }
 }
 
 impl SlipCodec {
@@ -16,53 +48,714 @@ impl SlipCodec {
     pub fn new() -> Self {
         Self::default()
     }
+}
 
+impl<O> SlipCodec<O> {
+    /// Report frames, escapes and errors to `observer`, replacing the current observer.
+    pub fn with_observer<P: Observer>(self, observer: P) -> SlipCodec<P> {
+        SlipCodec {
+            state: self.state,
+            buffer: self.buffer,
+            frame_open: self.frame_open,
+            open_len: self.open_len,
+            observer,
+        }
+    }
+
+    /// Borrow the observer.
+    pub fn observer(&self) -> &O {
+        &self.observer
+    }
+
+    /// Borrow the observer mutably.
+    pub fn observer_mut(&mut self) -> &mut O {
+        &mut self.observer
+    }
+
+    /// Check whether a partial frame has been started but not yet terminated.
+    pub fn has_open_frame(&self) -> bool {
+        self.frame_open
+    }
+}
+
+impl<O: Observer> SlipCodec<O> {
     /// Encode a byte slice without allocating.
     pub fn encode_slice(&mut self, item: &[u8], dst: &mut BytesMut) -> Result<()> {
-        let mut writer = BytesMutWriter(dst);
-        encode_into_writer(item.iter().copied(), &mut writer)
+        self.encode_partial(item, dst)?;
+        self.encode_finish(dst)
+    }
+
+    /// Escape a chunk of payload without terminating the frame.
+    ///
+    /// Further chunks extend the same frame until [`encode_finish`](Self::encode_finish)
+    /// appends the closing [`END`](crate::END). A complete frame passed to
+    /// [`Encoder::encode`] while a partial frame is open is appended to it and terminates it.
+    pub fn encode_partial(&mut self, item: &[u8], dst: &mut BytesMut) -> Result<()> {
+        let escapes = escape_runs(item, END, |run| dst.extend_from_slice(run));
+        for _ in 0..escapes {
+            self.observer.on_escape();
+        }
+        self.open_len += item.len();
+        self.frame_open = true;
+        Ok(())
+    }
+
+    /// Terminate a frame started with [`encode_partial`](Self::encode_partial).
+    ///
+    /// Does nothing when no partial frame is open, so it is safe to call before closing a stream.
+    pub fn encode_finish(&mut self, dst: &mut BytesMut) -> Result<()> {
+        if std::mem::take(&mut self.frame_open) {
+            dst.extend_from_slice(&[END]);
+            self.observer.on_frame(std::mem::take(&mut self.open_len));
+        }
+        Ok(())
+    }
+
+    /// [`Decoder::decode`] without notifying the observer of the result.
+    fn decode_unobserved(&mut self, src: &mut BytesMut) -> Result<Option<Vec<u8>>> {
+        while !src.is_empty() {
+            let byte = src.split_to(1)[0];
+            let escaped = self.state.last_was_esc;
+            let completed = process_byte(&mut self.state, byte, |value| self.buffer.push(value))?;
+            if escaped {
+                self.observer.on_escape();
+            }
+            if completed {
+                self.observer.on_frame(self.buffer.len());
+                return Ok(Some(std::mem::take(&mut self.buffer)));
+            }
+        }
+        Ok(None)
     }
 }
 
-impl Encoder for SlipCodec {
+impl<O: Observer> Encoder for SlipCodec<O> {
     type Item = Vec<u8>;
     type Error = SlipError;
 
     fn encode(&mut self, item: Self::Item, dst: &mut BytesMut) -> Result<()> {
-        let mut writer = BytesMutWriter(dst);
-        encode_into_writer(item, &mut writer)
+        self.encode_slice(&item, dst)
     }
 }
 
-impl Decoder for SlipCodec {
+impl<O: Observer> Decoder for SlipCodec<O> {
     type Item = Vec<u8>;
     type Error = SlipError;
 
     fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>> {
-        while !src.is_empty() {
-            let byte = src.split_to(1)[0];
-            let completed = process_byte(&mut self.state, byte, |value| self.buffer.push(value))?;
-            if completed {
-                return Ok(Some(std::mem::take(&mut self.buffer)));
-            }
+        let result = self.decode_unobserved(src);
+        if let Err(err) = &result {
+            self.observer.on_error(err);
         }
-        Ok(None)
+        result
     }
 
     fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>> {
         if let Some(frame) = self.decode(src)? {
             return Ok(Some(frame));
         }
-        if self.state.last_was_esc {
-            return Err(SlipError::IncompleteEscape);
-        }
-        if !self.buffer.is_empty() {
-            return Err(SlipError::UnexpectedEndOfFrame);
+        if let Err(err) = check_frame_closed(self.buffer.len(), self.state.last_was_esc) {
+            self.observer.on_error(&err);
+            return Err(err);
         }
         Ok(None)
     }
 }
 
+/// Frame writer over any [`AsyncWrite`] that never leaves the peer with an unterminated frame.
+///
+/// [`FramedWrite`](asynchronous_codec::FramedWrite) does not consult its encoder when the sink is
+/// closed, so a frame built from [`SlipCodec::encode_partial`] chunks would be cut off. This writer
+/// implements [`Sink<Vec<u8>>`](Sink) itself and appends the pending [`END`](crate::END) in both
+/// [`close`](AsyncSlipWriter::close) and `poll_close`.
+///
+/// As a [`Sink`], frames are buffered until `poll_flush`. Once 8 KiB or more are buffered,
+/// `poll_ready` writes them out first, so a slow peer applies backpressure instead of letting
+/// the buffer grow without bound.
+pub struct AsyncSlipWriter<W> {
+    inner: W,
+    codec: SlipCodec,
+    buffer: BytesMut,
+}
+
+impl<W> AsyncSlipWriter<W> {
+    /// Construct a new writer around the provided sink.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            codec: SlipCodec::new(),
+            buffer: BytesMut::new(),
+        }
+    }
+
+    /// Retrieve an immutable reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Retrieve a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consume the wrapper and return the inner writer, discarding any unwritten bytes.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Check whether a partial frame has been started but not yet terminated.
+    pub fn has_open_frame(&self) -> bool {
+        self.codec.has_open_frame()
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncSlipWriter<W> {
+    /// Encode the provided payload as a complete SLIP frame and write it to the underlying sink.
+    pub async fn write_frame(&mut self, payload: &[u8]) -> Result<()> {
+        self.codec.encode_slice(payload, &mut self.buffer)?;
+        self.write_buffer().await
+    }
+
+    /// Write a chunk of payload without terminating the frame.
+    pub async fn write_partial(&mut self, chunk: &[u8]) -> Result<()> {
+        self.codec.encode_partial(chunk, &mut self.buffer)?;
+        self.write_buffer().await
+    }
+
+    /// Terminate a frame started with [`write_partial`](Self::write_partial).
+    pub async fn end_frame(&mut self) -> Result<()> {
+        self.codec.encode_finish(&mut self.buffer)?;
+        self.write_buffer().await
+    }
+
+    /// Write a frame that was already encoded with [`encode_frame_shared`](crate::encode_frame_shared).
+    ///
+    /// The bytes are forwarded as-is without escaping them again, which lets one encoded frame
+    /// be fanned out to many writers. Fails with [`SlipError::FramePending`] while a frame
+    /// opened with [`write_partial`](Self::write_partial) is not terminated, as the shared frame
+    /// would otherwise be merged into it.
+    pub async fn send_shared(&mut self, frame: Bytes) -> Result<()> {
+        if self.has_open_frame() {
+            return Err(SlipError::FramePending);
+        }
+        self.write_buffer().await?;
+        let mut frame = frame;
+        poll_fn(|cx| poll_write_all(&mut self.inner, cx, &mut frame)).await
+    }
+
+    /// Flush buffered bytes and the underlying writer.
+    pub async fn flush(&mut self) -> Result<()> {
+        poll_fn(|cx| self.poll_flush_inner(cx)).await
+    }
+
+    /// Terminate any open frame, flush, and close the underlying writer.
+    pub async fn close(&mut self) -> Result<()> {
+        poll_fn(|cx| self.poll_close_inner(cx)).await
+    }
+
+    async fn write_buffer(&mut self) -> Result<()> {
+        poll_fn(|cx| self.poll_write_buffer(cx)).await
+    }
+
+    fn poll_write_buffer(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        poll_write_all(&mut self.inner, cx, &mut self.buffer)
+    }
+
+    fn poll_flush_inner(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        ready!(self.poll_write_buffer(cx))?;
+        Poll::Ready(ready!(Pin::new(&mut self.inner).poll_flush(cx)).map_err(SlipError::from))
+    }
+
+    fn poll_close_inner(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.codec.encode_finish(&mut self.buffer)?;
+        ready!(self.poll_write_buffer(cx))?;
+        Poll::Ready(ready!(Pin::new(&mut self.inner).poll_close(cx)).map_err(SlipError::from))
+    }
+}
+
+impl<W: AsyncWrite + Unpin> Sink<Vec<u8>> for AsyncSlipWriter<W> {
+    type Error = SlipError;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        if this.buffer.len() >= DEFAULT_FLUSH_THRESHOLD {
+            ready!(this.poll_write_buffer(cx))?;
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Vec<u8>) -> Result<()> {
+        let this = self.get_mut();
+        this.codec.encode(item, &mut this.buffer)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.get_mut().poll_flush_inner(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.get_mut().poll_close_inner(cx)
+    }
+}
+
+/// Encodes the frame into the write buffer without writing it; it goes out with the next
+/// write, [`flush`](AsyncSlipWriter::flush) or [`close`](AsyncSlipWriter::close).
+///
+/// Fails with [`SlipError::FramePending`] while a frame opened with
+/// [`write_partial`](AsyncSlipWriter::write_partial) is not terminated, and with an
+/// [`io::ErrorKind::WouldBlock`] error once 8 KiB or more are buffered, the point where
+/// `poll_ready` would wait for the buffer to be written.
+impl<W> FrameSink for AsyncSlipWriter<W> {
+    fn send_frame(&mut self, payload: &[u8]) -> Result<()> {
+        if self.has_open_frame() {
+            return Err(SlipError::FramePending);
+        }
+        if self.buffer.len() >= DEFAULT_FLUSH_THRESHOLD {
+            return Err(io::Error::from(io::ErrorKind::WouldBlock).into());
+        }
+        self.codec.encode_slice(payload, &mut self.buffer)
+    }
+}
+
+/// Async counterpart of [`BufferedSlipWriter`](crate::BufferedSlipWriter) that coalesces
+/// frames into fewer writes to the underlying [`AsyncWrite`].
+///
+/// Frames are encoded into an internal buffer which is written out once it holds at least
+/// `threshold` bytes (8 KiB by default) or when [`flush`](Self::flush) is called.
+///
+/// Drop cannot await, so buffered frames must be written with [`flush`](Self::flush),
+/// [`close`](Self::close) or [`finish`](Self::finish) before the writer goes away; a successful
+/// call leaves [`buffered_len`](Self::buffered_len) at zero. Dropping the writer discards
+/// anything still buffered, e.g. after one of those calls failed or was cancelled, so check
+/// [`buffered_len`](Self::buffered_len) first if unwritten frames must not go unnoticed.
+///
+/// ```
+/// use futures::executor::block_on;
+/// use slipspeed::async_codec::BufferedAsyncSlipWriter;
+/// use slipspeed::decode_frames;
+///
+/// block_on(async {
+///     let mut writer = BufferedAsyncSlipWriter::new(Vec::new());
+///     writer.write_frame(b"ping").await.unwrap();
+///     writer.write_frame(b"pong").await.unwrap();
+///     assert!(writer.get_ref().is_empty());
+///     let encoded = writer.finish().await.unwrap();
+///     assert_eq!(decode_frames(&encoded).unwrap(), [b"ping", b"pong"]);
+/// });
+/// ```
+pub struct BufferedAsyncSlipWriter<W> {
+    inner: W,
+    buffer: BytesMut,
+    threshold: usize,
+}
+
+impl<W> BufferedAsyncSlipWriter<W> {
+    /// Construct a buffered writer with the default 8 KiB flush threshold.
+    pub fn new(inner: W) -> Self {
+        Self::with_threshold(inner, DEFAULT_FLUSH_THRESHOLD)
+    }
+
+    /// Construct a buffered writer that flushes once `threshold` bytes are buffered.
+    pub fn with_threshold(inner: W, threshold: usize) -> Self {
+        Self {
+            inner,
+            buffer: BytesMut::with_capacity(threshold),
+            threshold,
+        }
+    }
+
+    /// Number of buffered bytes after which the buffer is written out.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Number of encoded bytes currently held in the buffer.
+    pub fn buffered_len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Retrieve an immutable reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Retrieve a mutable reference to the underlying writer.
+    ///
+    /// Writing to it directly bypasses buffered frames and may reorder output.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+}
+
+impl<W: AsyncWrite + Unpin> BufferedAsyncSlipWriter<W> {
+    /// Encode the provided payload as a SLIP frame and append it to the buffer.
+    ///
+    /// The buffer is written to the underlying writer once it reaches the flush threshold.
+    pub async fn write_frame(&mut self, payload: &[u8]) -> Result<()> {
+        self.buffer.reserve(encoded_frame_len(payload));
+        encode_into_writer(
+            payload.iter().copied(),
+            &mut BytesMutWriter(&mut self.buffer),
+        )?;
+        if self.buffer.len() >= self.threshold {
+            self.write_buffer().await?;
+        }
+        Ok(())
+    }
+
+    /// Write all buffered frames and flush the underlying writer.
+    pub async fn flush(&mut self) -> Result<()> {
+        self.write_buffer().await?;
+        let inner = self.get_mut();
+        poll_fn(|cx| Pin::new(&mut *inner).poll_flush(cx)).await?;
+        Ok(())
+    }
+
+    /// Write all buffered frames and close the underlying writer.
+    pub async fn close(&mut self) -> Result<()> {
+        self.write_buffer().await?;
+        let inner = self.get_mut();
+        poll_fn(|cx| Pin::new(&mut *inner).poll_close(cx)).await?;
+        Ok(())
+    }
+
+    /// Flush all buffered frames and return the underlying writer.
+    ///
+    /// On error the writer is dropped together with the frames that were not written.
+    pub async fn finish(mut self) -> Result<W> {
+        self.flush().await?;
+        Ok(self.inner)
+    }
+
+    async fn write_buffer(&mut self) -> Result<()> {
+        let (inner, buffer) = (&mut self.inner, &mut self.buffer);
+        poll_fn(|cx| poll_write_all(inner, cx, buffer)).await
+    }
+}
+
+/// Wrapper around [`AsyncSlipWriter`] that terminates a partially written frame once no more
+/// payload arrived for a configurable idle period.
+///
+/// This is meant for incremental encoding with [`write_partial`](FlushOnIdle::write_partial),
+/// which is built on [`SlipCodec::encode_partial`], where the producer does not know in
+/// advance where a frame ends. Timers come from tokio, so
+/// the wrapper must be used inside a tokio runtime with the time driver enabled.
+///
+/// Nothing happens in the background: await [`idle`](FlushOnIdle::idle) alongside the
+/// producer, e.g. in `tokio::select!`. It completes after terminating and flushing the frame
+/// once the idle period has passed since the last chunk. Dropping it before then is
+/// harmless, so it can be raced against new input.
+///
+/// ```
+/// use slipspeed::async_codec::{AsyncSlipWriter, FlushOnIdle};
+/// use slipspeed::encode_frame;
+/// use std::time::Duration;
+///
+/// # tokio::runtime::Builder::new_current_thread().enable_time().start_paused(true).build().unwrap().block_on(async {
+/// let mut writer = FlushOnIdle::new(AsyncSlipWriter::new(Vec::new()), Duration::from_millis(20));
+/// writer.write_partial(b"temp=21").await.unwrap();
+/// writer.idle().await.unwrap();
+/// assert_eq!(writer.get_ref().get_ref(), &encode_frame(b"temp=21"));
+/// # });
+/// ```
+#[cfg(feature = "idle-flush")]
+pub struct FlushOnIdle<W> {
+    writer: AsyncSlipWriter<W>,
+    idle: Duration,
+    deadline: Option<Instant>,
+}
+
+#[cfg(feature = "idle-flush")]
+impl<W> FlushOnIdle<W> {
+    /// Wrap `writer`, terminating open frames after `idle` without new payload.
+    pub fn new(writer: AsyncSlipWriter<W>, idle: Duration) -> Self {
+        Self {
+            writer,
+            idle,
+            deadline: None,
+        }
+    }
+
+    /// The idle period after which an open frame is terminated.
+    pub fn idle_timeout(&self) -> Duration {
+        self.idle
+    }
+
+    /// Retrieve an immutable reference to the wrapped writer.
+    pub fn get_ref(&self) -> &AsyncSlipWriter<W> {
+        &self.writer
+    }
+
+    /// Retrieve a mutable reference to the wrapped writer.
+    ///
+    /// Frames written or terminated through it do not reset the idle timer.
+    pub fn get_mut(&mut self) -> &mut AsyncSlipWriter<W> {
+        &mut self.writer
+    }
+
+    /// Consume the wrapper and return the wrapped writer, leaving any open frame open.
+    pub fn into_inner(self) -> AsyncSlipWriter<W> {
+        self.writer
+    }
+}
+
+#[cfg(feature = "idle-flush")]
+impl<W: AsyncWrite + Unpin> FlushOnIdle<W> {
+    /// Write a chunk of payload without terminating the frame and restart the idle timer.
+    pub async fn write_partial(&mut self, chunk: &[u8]) -> Result<()> {
+        self.writer.write_partial(chunk).await?;
+        self.deadline = Some(Instant::now() + self.idle);
+        Ok(())
+    }
+
+    /// Terminate the open frame right away and stop the idle timer.
+    pub async fn end_frame(&mut self) -> Result<()> {
+        self.writer.end_frame().await?;
+        self.deadline = None;
+        Ok(())
+    }
+
+    /// Write a complete frame, which also terminates any open frame, and stop the idle timer.
+    pub async fn write_frame(&mut self, payload: &[u8]) -> Result<()> {
+        self.writer.write_frame(payload).await?;
+        self.deadline = None;
+        Ok(())
+    }
+
+    /// Wait until the open frame has been idle for the configured period, then terminate
+    /// and flush it.
+    ///
+    /// Never completes while no frame is open.
+    pub async fn idle(&mut self) -> Result<()> {
+        let Some(deadline) = self.deadline else {
+            return std::future::pending().await;
+        };
+        tokio::time::sleep_until(deadline).await;
+        self.writer.end_frame().await?;
+        self.deadline = None;
+        self.writer.flush().await
+    }
+
+    /// Terminate any open frame, flush, and close the underlying writer.
+    pub async fn close(&mut self) -> Result<()> {
+        self.writer.close().await?;
+        self.deadline = None;
+        Ok(())
+    }
+}
+
+/// Size of the input buffer used by [`AsyncSlipReader`].
+const READ_CAPACITY: usize = 8 * 1024;
+
+/// Frame reader over any [`AsyncRead`], the async counterpart of [`SlipReader`](crate::SlipReader).
+///
+/// Input is read in blocks of up to 8 KiB, so the reader may consume bytes past the frame it
+/// returns; they are kept for the next call. If a call is cancelled mid-frame, resume with the
+/// same method so the frame stays intact.
+pub struct AsyncSlipReader<R> {
+    inner: R,
+    state: DecoderState,
+    pending: Vec<u8>,
+    length: usize,
+    read_buf: Box<[u8]>,
+    read_pos: usize,
+    read_filled: usize,
+}
+
+impl<R> AsyncSlipReader<R> {
+    /// Construct a new reader around the provided source.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            state: DecoderState::default(),
+            pending: Vec::new(),
+            length: 0,
+            read_buf: vec![0; READ_CAPACITY].into_boxed_slice(),
+            read_pos: 0,
+            read_filled: 0,
+        }
+    }
+
+    /// Retrieve an immutable reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Retrieve a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consume the wrapper and return the inner reader, discarding any buffered input.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Turn the reader into a [`SlipFrameStream`], keeping any buffered input.
+    pub fn into_stream(self) -> SlipFrameStream<R> {
+        SlipFrameStream {
+            reader: self,
+            done: false,
+        }
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncSlipReader<R> {
+    /// Read the next SLIP frame, or `Ok(None)` once the source ends on a frame boundary.
+    pub async fn read_frame(&mut self) -> Result<Option<Vec<u8>>> {
+        match self.next_frame(true).await? {
+            Some(_) => Ok(Some(std::mem::take(&mut self.pending))),
+            None => Ok(None),
+        }
+    }
+
+    /// Read the next SLIP frame and return only its decoded length.
+    ///
+    /// The frame is consumed from the stream but its payload is never stored, which lets a
+    /// consumer decide whether to allocate based on size.
+    pub async fn read_frame_length(&mut self) -> Result<Option<usize>> {
+        let length = self.next_frame(false).await?;
+        self.pending.clear();
+        Ok(length)
+    }
+
+    /// Decode and discard up to `n` frames, returning how many were skipped before the source
+    /// ended on a frame boundary.
+    ///
+    /// Payloads are never stored, so skipping ahead in a capture costs no allocations beyond
+    /// the input buffer.
+    pub async fn skip_frames(&mut self, n: usize) -> Result<usize> {
+        for skipped in 0..n {
+            if self.read_frame_length().await?.is_none() {
+                return Ok(skipped);
+            }
+        }
+        Ok(n)
+    }
+
+    async fn next_frame(&mut self, store: bool) -> Result<Option<usize>> {
+        poll_fn(|cx| self.poll_frame(cx, store)).await
+    }
+
+    /// Decode input until a frame completes and return its length, storing the payload in
+    /// `pending` if `store` is set.
+    fn poll_frame(&mut self, cx: &mut Context<'_>, store: bool) -> Poll<Result<Option<usize>>> {
+        loop {
+            if self.read_pos == self.read_filled {
+                let read = ready!(Pin::new(&mut self.inner).poll_read(cx, &mut self.read_buf))?;
+                if read == 0 {
+                    check_frame_closed(self.length, self.state.last_was_esc)?;
+                    return Poll::Ready(Ok(None));
+                }
+                self.read_pos = 0;
+                self.read_filled = read;
+            }
+
+            while self.read_pos < self.read_filled {
+                let byte = self.read_buf[self.read_pos];
+                self.read_pos += 1;
+                let completed = process_byte(&mut self.state, byte, |value| {
+                    self.length += 1;
+                    if store {
+                        self.pending.push(value);
+                    }
+                })?;
+                if completed {
+                    return Poll::Ready(Ok(Some(std::mem::take(&mut self.length))));
+                }
+            }
+        }
+    }
+}
+
+/// [`Stream`] of the SLIP frames read from an [`AsyncRead`], without a `FramedRead`.
+///
+/// Input is buffered like in [`AsyncSlipReader`]. The stream ends once the source ends on a
+/// frame boundary; after yielding an error it ends as well.
+///
+/// ```
+/// use futures::{executor::block_on, io::Cursor, StreamExt};
+/// use slipspeed::{async_codec::SlipFrameStream, encode_frame};
+///
+/// let encoded = [encode_frame(b"one"), encode_frame(b"two")].concat();
+/// let mut stream = SlipFrameStream::new(Cursor::new(encoded));
+/// block_on(async {
+///     while let Some(frame) = stream.next().await {
+///         assert_eq!(frame.unwrap().len(), 3);
+///     }
+/// });
+/// ```
+pub struct SlipFrameStream<R> {
+    reader: AsyncSlipReader<R>,
+    done: bool,
+}
+
+impl<R> SlipFrameStream<R> {
+    /// Construct a stream decoding frames from the provided source.
+    pub fn new(inner: R) -> Self {
+        AsyncSlipReader::new(inner).into_stream()
+    }
+
+    /// Retrieve an immutable reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        self.reader.get_ref()
+    }
+
+    /// Retrieve a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        self.reader.get_mut()
+    }
+
+    /// Consume the stream and return the inner reader, discarding any buffered input.
+    pub fn into_inner(self) -> R {
+        self.reader.into_inner()
+    }
+}
+
+impl<R: AsyncRead + Unpin> Stream for SlipFrameStream<R> {
+    type Item = Result<Vec<u8>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.done {
+            return Poll::Ready(None);
+        }
+        match ready!(this.reader.poll_frame(cx, true)) {
+            Ok(Some(_)) => Poll::Ready(Some(Ok(std::mem::take(&mut this.reader.pending)))),
+            Ok(None) => {
+                this.done = true;
+                Poll::Ready(None)
+            }
+            Err(err) => {
+                this.done = true;
+                Poll::Ready(Some(Err(err)))
+            }
+        }
+    }
+}
+
+impl<R: AsyncRead + Unpin> FusedStream for SlipFrameStream<R> {
+    fn is_terminated(&self) -> bool {
+        self.done
+    }
+}
+
+/// Write all remaining bytes of `buf` to `inner`, advancing `buf` as bytes are accepted.
+fn poll_write_all<W: AsyncWrite + Unpin, B: Buf>(
+    inner: &mut W,
+    cx: &mut Context<'_>,
+    buf: &mut B,
+) -> Poll<Result<()>> {
+    while buf.has_remaining() {
+        let written = ready!(Pin::new(&mut *inner).poll_write(cx, buf.chunk()))?;
+        if written == 0 {
+            return Poll::Ready(Err(io::Error::from(io::ErrorKind::WriteZero).into()));
+        }
+        buf.advance(written);
+    }
+    Poll::Ready(Ok(()))
+}
+
 struct BytesMutWriter<'a>(&'a mut BytesMut);
 
 impl<'a> Write for BytesMutWriter<'a> {
@@ -80,6 +773,147 @@ impl<'a> Write for BytesMutWriter<'a> {
 mod tests {
     use super::*;
 
+    /// Vec-backed [`AsyncWrite`] that counts the `poll_write` calls reaching it.
+    #[derive(Default)]
+    struct CountingWriter {
+        data: Vec<u8>,
+        writes: usize,
+        fail: bool,
+    }
+
+    impl AsyncWrite for CountingWriter {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            let this = self.get_mut();
+            if this.fail {
+                return Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()));
+            }
+            this.writes += 1;
+            this.data.extend_from_slice(buf);
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[cfg(feature = "idle-flush")]
+    #[tokio::test(start_paused = true)]
+    async fn idle_writer_terminates_frame_after_timeout() {
+        use std::time::Duration;
+        use tokio::time::{timeout, Instant};
+
+        let idle = Duration::from_millis(50);
+        let mut writer = FlushOnIdle::new(AsyncSlipWriter::new(Vec::new()), idle);
+        assert!(timeout(Duration::from_secs(1), writer.idle())
+            .await
+            .is_err());
+
+        writer.write_partial(b"par").await.unwrap();
+        assert!(timeout(Duration::from_millis(49), writer.idle())
+            .await
+            .is_err());
+        assert_eq!(writer.get_ref().get_ref(), b"par");
+
+        writer.write_partial(&[crate::END]).await.unwrap();
+        let start = Instant::now();
+        writer.idle().await.unwrap();
+        assert_eq!(start.elapsed(), idle);
+        assert_eq!(
+            writer.get_ref().get_ref(),
+            &crate::encode_frame(&[b'p', b'a', b'r', crate::END])
+        );
+        assert!(!writer.get_ref().has_open_frame());
+
+        writer.write_partial(b"x").await.unwrap();
+        writer.end_frame().await.unwrap();
+        assert!(timeout(Duration::from_secs(1), writer.idle())
+            .await
+            .is_err());
+        assert!(writer
+            .get_ref()
+            .get_ref()
+            .ends_with(&crate::encode_frame(b"x")));
+    }
+
+    #[test]
+    fn buffered_writer_coalesces_frames() {
+        use futures::executor::block_on;
+
+        block_on(async {
+            let frames: Vec<Vec<u8>> = (0u8..100).map(|i| vec![i, crate::END, i]).collect();
+
+            let mut writer = BufferedAsyncSlipWriter::with_threshold(CountingWriter::default(), 64);
+            for frame in &frames {
+                writer.write_frame(frame).await.unwrap();
+            }
+            assert!(writer.buffered_len() < 64);
+            let sink = writer.finish().await.unwrap();
+            assert_eq!(crate::decode_frames(&sink.data).unwrap(), frames);
+            // 500 encoded bytes in chunks of at least 64 bytes.
+            assert!(sink.writes <= 8, "{} writes", sink.writes);
+
+            let mut unbuffered = AsyncSlipWriter::new(CountingWriter::default());
+            for frame in &frames {
+                unbuffered.write_frame(frame).await.unwrap();
+            }
+            assert_eq!(unbuffered.get_ref().data, sink.data);
+            assert_eq!(unbuffered.get_ref().writes, frames.len());
+
+            let mut writer = BufferedAsyncSlipWriter::new(CountingWriter::default());
+            writer.write_frame(b"held").await.unwrap();
+            assert_eq!(writer.get_ref().writes, 0);
+            writer.close().await.unwrap();
+            assert_eq!(writer.get_ref().data, crate::encode_frame(b"held"));
+            assert_eq!(writer.get_ref().writes, 1);
+        });
+    }
+
+    #[test]
+    fn buffered_writer_failed_finish_returns_error() {
+        use futures::executor::block_on;
+
+        let mut writer = BufferedAsyncSlipWriter::new(CountingWriter {
+            fail: true,
+            ..CountingWriter::default()
+        });
+        block_on(writer.write_frame(b"lost")).unwrap();
+        assert_eq!(writer.buffered_len(), 5);
+        assert!(matches!(block_on(writer.finish()), Err(SlipError::Io(_))));
+    }
+
+    #[test]
+    fn buffered_writer_drop_after_flush_close_or_finish() {
+        use futures::executor::block_on;
+
+        block_on(async {
+            let mut writer = BufferedAsyncSlipWriter::new(CountingWriter::default());
+            writer.write_frame(b"one").await.unwrap();
+            writer.flush().await.unwrap();
+            assert_eq!(writer.buffered_len(), 0);
+            drop(writer);
+
+            let mut writer = BufferedAsyncSlipWriter::new(CountingWriter::default());
+            writer.write_frame(b"two").await.unwrap();
+            writer.close().await.unwrap();
+            assert_eq!(writer.buffered_len(), 0);
+            drop(writer);
+
+            let mut writer = BufferedAsyncSlipWriter::new(CountingWriter::default());
+            writer.write_frame(b"three").await.unwrap();
+            let inner = writer.finish().await.unwrap();
+            assert_eq!(inner.data, crate::encode_frame(b"three"));
+        });
+    }
+
     #[test]
     fn encode_vec() {
         let mut codec = SlipCodec::new();
@@ -112,6 +946,27 @@ mod tests {
         assert!(codec.decode(&mut src).unwrap().is_none());
     }
 
+    #[test]
+    fn framed_read_joins_escape_split_across_polls() {
+        use crate::{END, ESC, ESC_END};
+        use asynchronous_codec::FramedRead;
+        use futures::executor::block_on;
+        use futures::{stream, StreamExt, TryStreamExt};
+
+        let chunks: Vec<io::Result<Vec<u8>>> = vec![
+            Ok(vec![b'a', ESC]),
+            Ok(vec![ESC_END, b'b', END, ESC]),
+            Ok(vec![ESC_END, END]),
+        ];
+        let reader = stream::iter(chunks).into_async_read();
+        let frames: Vec<Vec<u8>> = block_on(
+            FramedRead::new(reader, SlipCodec::new())
+                .map(|frame| frame.unwrap())
+                .collect(),
+        );
+        assert_eq!(frames, vec![vec![b'a', END, b'b'], vec![END]]);
+    }
+
     #[test]
     fn decode_incomplete_eof_errors() {
         let mut frame = crate::encode_frame(b"broken");
@@ -120,7 +975,10 @@ mod tests {
         let mut src = BytesMut::from(&frame[..]);
         assert!(codec.decode(&mut src).unwrap().is_none());
         let err = codec.decode_eof(&mut src).unwrap_err();
-        assert!(matches!(err, SlipError::UnexpectedEndOfFrame));
+        assert!(matches!(
+            err,
+            SlipError::UnexpectedEndOfFrame { buffered: 6 }
+        ));
     }
 
     #[test]
@@ -129,6 +987,306 @@ mod tests {
         let mut src = BytesMut::from(&[crate::ESC][..]);
         assert!(codec.decode(&mut src).unwrap().is_none());
         let err = codec.decode_eof(&mut src).unwrap_err();
-        assert!(matches!(err, SlipError::IncompleteEscape));
+        assert!(matches!(err, SlipError::IncompleteEscape { buffered: 0 }));
+    }
+
+    #[test]
+    fn encode_partial_then_finish() {
+        let mut codec = SlipCodec::new();
+        let mut dst = BytesMut::new();
+        codec.encode_partial(&[b'a', crate::END], &mut dst).unwrap();
+        assert!(codec.has_open_frame());
+        codec.encode_partial(b"b", &mut dst).unwrap();
+        codec.encode_finish(&mut dst).unwrap();
+        codec.encode_finish(&mut dst).unwrap();
+        assert!(!codec.has_open_frame());
+        assert_eq!(&dst[..], crate::encode_frame(&[b'a', crate::END, b'b']));
+    }
+
+    #[test]
+    fn writer_close_terminates_partial_frame() {
+        use futures::executor::block_on;
+        use futures::io::Cursor;
+
+        let mut writer = AsyncSlipWriter::new(Cursor::new(Vec::new()));
+        block_on(async {
+            writer.write_frame(b"one").await.unwrap();
+            writer.write_partial(b"tw").await.unwrap();
+            writer.write_partial(b"o").await.unwrap();
+            writer.close().await.unwrap();
+        });
+        let written = writer.into_inner().into_inner();
+        assert_eq!(written.last(), Some(&crate::END));
+        assert_eq!(
+            crate::decode_frames(&written).unwrap(),
+            vec![b"one".to_vec(), b"two".to_vec()]
+        );
+    }
+
+    #[test]
+    fn sink_ready_writes_out_full_buffer() {
+        use futures::executor::block_on;
+        use futures::SinkExt;
+
+        let mut writer = AsyncSlipWriter::new(Vec::new());
+        block_on(async {
+            writer.feed(b"small".to_vec()).await.unwrap();
+            writer.feed(b"next".to_vec()).await.unwrap();
+            assert!(writer.get_ref().is_empty());
+            writer.feed(vec![7; DEFAULT_FLUSH_THRESHOLD]).await.unwrap();
+            assert!(writer.get_ref().is_empty());
+            writer.feed(b"last".to_vec()).await.unwrap();
+            assert_eq!(writer.get_ref().len(), DEFAULT_FLUSH_THRESHOLD + 12);
+            writer.flush().await.unwrap();
+        });
+        let frames = crate::decode_frames(writer.get_ref()).unwrap();
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[3], b"last");
+    }
+
+    #[test]
+    fn sink_close_terminates_partial_frame() {
+        use futures::executor::block_on;
+        use futures::io::Cursor;
+        use futures::SinkExt;
+
+        let mut writer = AsyncSlipWriter::new(Cursor::new(Vec::new()));
+        block_on(async {
+            writer.send(b"first".to_vec()).await.unwrap();
+            writer.write_partial(b"cut").await.unwrap();
+            SinkExt::close(&mut writer).await.unwrap();
+        });
+        let written = writer.into_inner().into_inner();
+        assert_eq!(written.last(), Some(&crate::END));
+        assert_eq!(
+            crate::decode_frames(&written).unwrap(),
+            vec![b"first".to_vec(), b"cut".to_vec()]
+        );
+    }
+
+    #[test]
+    fn reader_reports_frame_lengths() {
+        use futures::executor::block_on;
+        use futures::io::Cursor;
+
+        let payloads: [&[u8]; 4] = [b"abc", &[], &[crate::END, crate::ESC, 1], &[7; 20_000]];
+        let encoded: Vec<u8> = payloads
+            .iter()
+            .flat_map(|payload| crate::encode_frame(payload))
+            .collect();
+        let mut reader = AsyncSlipReader::new(Cursor::new(encoded));
+        block_on(async {
+            assert_eq!(reader.read_frame_length().await.unwrap(), Some(3));
+            assert_eq!(reader.read_frame().await.unwrap(), Some(Vec::new()));
+            assert_eq!(reader.read_frame_length().await.unwrap(), Some(3));
+            assert_eq!(reader.read_frame_length().await.unwrap(), Some(20_000));
+            assert_eq!(reader.read_frame_length().await.unwrap(), None);
+        });
+    }
+
+    #[test]
+    fn reader_skips_frames_then_reads_next() {
+        use futures::executor::block_on;
+        use futures::io::Cursor;
+
+        let encoded: Vec<u8> = (0..5u8)
+            .flat_map(|n| crate::encode_frame(&[n, crate::END, n]))
+            .collect();
+        let mut reader = AsyncSlipReader::new(Cursor::new(encoded));
+        block_on(async {
+            assert_eq!(reader.skip_frames(0).await.unwrap(), 0);
+            assert_eq!(reader.skip_frames(3).await.unwrap(), 3);
+            assert_eq!(
+                reader.read_frame().await.unwrap(),
+                Some(vec![3, crate::END, 3])
+            );
+            assert_eq!(reader.skip_frames(10).await.unwrap(), 1);
+            assert_eq!(reader.read_frame().await.unwrap(), None);
+        });
+    }
+
+    #[test]
+    fn reader_length_errors_on_truncated_frame() {
+        use futures::executor::block_on;
+        use futures::io::Cursor;
+
+        let mut reader = AsyncSlipReader::new(Cursor::new(vec![b'a', crate::END, b'b', b'c']));
+        block_on(async {
+            assert_eq!(reader.read_frame().await.unwrap(), Some(b"a".to_vec()));
+            let err = reader.read_frame_length().await.unwrap_err();
+            assert!(matches!(
+                err,
+                SlipError::UnexpectedEndOfFrame { buffered: 2 }
+            ));
+        });
+    }
+
+    #[test]
+    fn frame_stream_yields_frames_from_chunked_reader() {
+        use crate::{END, ESC};
+        use futures::executor::block_on;
+        use futures::{stream, StreamExt, TryStreamExt};
+
+        let encoded = [
+            crate::encode_frame(b"first"),
+            crate::encode_frame(&[ESC, END]),
+            crate::encode_frame(b""),
+        ]
+        .concat();
+        let chunks: Vec<io::Result<Vec<u8>>> = encoded.chunks(3).map(|c| Ok(c.to_vec())).collect();
+        let mut frames = SlipFrameStream::new(stream::iter(chunks).into_async_read());
+        block_on(async {
+            assert_eq!(frames.next().await.unwrap().unwrap(), b"first");
+            assert_eq!(frames.next().await.unwrap().unwrap(), vec![ESC, END]);
+            assert_eq!(frames.next().await.unwrap().unwrap(), Vec::<u8>::new());
+            assert!(frames.next().await.is_none());
+            assert!(frames.is_terminated());
+        });
+
+        let chunks: Vec<io::Result<Vec<u8>>> = vec![Ok(vec![b'a', END, b'x']), Ok(vec![ESC])];
+        let results: Vec<Result<Vec<u8>>> =
+            block_on(SlipFrameStream::new(stream::iter(chunks).into_async_read()).collect());
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap(), b"a");
+        assert!(matches!(
+            results[1],
+            Err(SlipError::IncompleteEscape { buffered: 1 })
+        ));
+    }
+
+    #[test]
+    fn send_shared_fans_out_one_encoding() {
+        use futures::executor::block_on;
+        use futures::io::Cursor;
+
+        let frame = crate::encode_frame_shared(&[b'x', crate::END, crate::ESC]);
+        let mut writers: Vec<_> = (0..3)
+            .map(|_| AsyncSlipWriter::new(Cursor::new(Vec::new())))
+            .collect();
+        block_on(async {
+            for writer in &mut writers {
+                let handle = frame.clone();
+                assert_eq!(handle.as_ptr(), frame.as_ptr());
+                writer.write_partial(b"pre").await.unwrap();
+                writer.end_frame().await.unwrap();
+                writer.send_shared(handle).await.unwrap();
+                assert!(!writer.has_open_frame());
+            }
+        });
+        for writer in writers {
+            let written = writer.into_inner().into_inner();
+            assert_eq!(
+                crate::decode_frames(&written).unwrap(),
+                vec![b"pre".to_vec(), vec![b'x', crate::END, crate::ESC]]
+            );
+        }
+    }
+
+    #[test]
+    fn send_shared_rejects_open_frame() {
+        use futures::executor::block_on;
+        use futures::io::Cursor;
+
+        let mut writer = AsyncSlipWriter::new(Cursor::new(Vec::new()));
+        block_on(async {
+            writer.write_partial(b"half").await.unwrap();
+            let shared = crate::encode_frame_shared(b"shared");
+            assert!(matches!(
+                writer.send_shared(shared.clone()).await,
+                Err(SlipError::FramePending)
+            ));
+            writer.end_frame().await.unwrap();
+            writer.send_shared(shared).await.unwrap();
+        });
+        let written = writer.into_inner().into_inner();
+        assert_eq!(
+            crate::decode_frames(&written).unwrap(),
+            vec![b"half".to_vec(), b"shared".to_vec()]
+        );
+    }
+
+    #[test]
+    fn observer_sees_codec_events() {
+        #[derive(Default)]
+        struct Events {
+            frames: Vec<usize>,
+            escapes: usize,
+            errors: usize,
+        }
+
+        impl Observer for Events {
+            fn on_frame(&mut self, len: usize) {
+                self.frames.push(len);
+            }
+
+            fn on_error(&mut self, _error: &SlipError) {
+                self.errors += 1;
+            }
+
+            fn on_escape(&mut self) {
+                self.escapes += 1;
+            }
+        }
+
+        let mut codec = SlipCodec::new().with_observer(Events::default());
+        let mut dst = BytesMut::new();
+        codec.encode_partial(&[END, b'a'], &mut dst).unwrap();
+        codec.encode_partial(b"bc", &mut dst).unwrap();
+        codec.encode(vec![crate::ESC], &mut dst).unwrap();
+        assert_eq!(codec.observer().frames, vec![5]);
+        assert_eq!(codec.observer().escapes, 2);
+
+        assert_eq!(
+            codec.decode(&mut dst).unwrap(),
+            Some(vec![END, b'a', b'b', b'c', crate::ESC])
+        );
+        let mut src = BytesMut::from(&[b'x', crate::ESC, 0x01][..]);
+        assert!(codec.decode(&mut src).is_err());
+
+        let events = codec.observer();
+        assert_eq!(events.frames, vec![5, 5]);
+        assert_eq!(events.escapes, 4);
+        assert_eq!(events.errors, 1);
+    }
+
+    #[test]
+    fn writer_is_a_frame_sink() {
+        use crate::{send_all, SlipWriter};
+        use futures::executor::block_on;
+        use futures::io::Cursor;
+
+        let mut writer = AsyncSlipWriter::new(Cursor::new(Vec::new()));
+        let mut plain = SlipWriter::new(Vec::new());
+        for sink in [&mut writer as &mut dyn FrameSink, &mut plain] {
+            send_all(sink, [&b"a"[..], &[END, crate::ESC]]).unwrap();
+        }
+        assert!(writer.get_ref().get_ref().is_empty());
+        block_on(writer.flush()).unwrap();
+        assert_eq!(writer.into_inner().into_inner(), plain.into_inner());
+    }
+
+    #[test]
+    fn frame_sink_refuses_open_frame_and_full_buffer() {
+        use futures::executor::block_on;
+        use futures::io::Cursor;
+
+        let mut writer = AsyncSlipWriter::new(Cursor::new(Vec::new()));
+        block_on(writer.write_partial(b"open")).unwrap();
+        assert!(matches!(
+            writer.send_frame(b"x"),
+            Err(SlipError::FramePending)
+        ));
+        block_on(writer.end_frame()).unwrap();
+
+        writer.send_frame(&[0; DEFAULT_FLUSH_THRESHOLD]).unwrap();
+        assert!(matches!(
+            writer.send_frame(b"x"),
+            Err(SlipError::Io(err)) if err.kind() == io::ErrorKind::WouldBlock
+        ));
+        block_on(writer.flush()).unwrap();
+        writer.send_frame(b"x").unwrap();
+        block_on(writer.flush()).unwrap();
+        let written = writer.into_inner().into_inner();
+        assert_eq!(crate::decode_frames(&written).unwrap().len(), 3);
     }
 }
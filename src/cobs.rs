@@ -0,0 +1,94 @@
+use crate::{decode_frame, encode_frame, Result, SlipError};
+
+/// Delimiter terminating a COBS frame on the wire.
+const COBS_DELIMITER: u8 = 0x00;
+
+/// Canonical COBS encoding of the empty payload, which the `cobs` crate does not produce.
+const COBS_EMPTY: [u8; 1] = [0x01];
+
+/// Transcode a single SLIP-encoded frame into a COBS frame terminated by a zero byte.
+///
+/// The payload is recovered with [`decode_frame`], so the same errors apply.
+///
+/// ```
+/// use slipspeed::{cobs::slip_to_cobs, encode_frame};
+///
+/// let cobs = slip_to_cobs(&encode_frame(&[0x11, 0x00, 0x22])).unwrap();
+/// assert_eq!(cobs, [0x02, 0x11, 0x02, 0x22, 0x00]);
+/// ```
+pub fn slip_to_cobs(slip_frame: &[u8]) -> Result<Vec<u8>> {
+    let payload = decode_frame(slip_frame)?;
+    let mut cobs = if payload.is_empty() {
+        COBS_EMPTY.to_vec()
+    } else {
+        ::cobs::encode_vec(&payload)
+    };
+    cobs.push(COBS_DELIMITER);
+    Ok(cobs)
+}
+
+/// Transcode a single COBS frame into a SLIP-encoded frame.
+///
+/// The trailing zero delimiter of `cobs_frame` is optional.
+///
+/// # Errors
+///
+/// Returns [`SlipError::InvalidCobsFrame`] if `cobs_frame` is empty or not valid COBS.
+///
+/// ```
+/// use slipspeed::{cobs::cobs_to_slip, encode_frame};
+///
+/// let slip = cobs_to_slip(&[0x02, 0x11, 0x02, 0x22, 0x00]).unwrap();
+/// assert_eq!(slip, encode_frame(&[0x11, 0x00, 0x22]));
+/// ```
+pub fn cobs_to_slip(cobs_frame: &[u8]) -> Result<Vec<u8>> {
+    let body = cobs_frame
+        .strip_suffix(&[COBS_DELIMITER])
+        .unwrap_or(cobs_frame);
+    if body == COBS_EMPTY {
+        return Ok(encode_frame(&[]));
+    }
+    let payload = ::cobs::decode_vec(body).map_err(|_| SlipError::InvalidCobsFrame)?;
+    Ok(encode_frame(&payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{END, ESC};
+
+    #[test]
+    fn transcoding_roundtrips_both_ways() {
+        let payloads: [&[u8]; 5] = [
+            b"",
+            b"plain",
+            &[END, ESC, 0x00, 0x00, END],
+            &[0x00; 300],
+            &[0x42; 600],
+        ];
+        for payload in payloads {
+            let slip = encode_frame(payload);
+            let cobs = slip_to_cobs(&slip).unwrap();
+            assert_eq!(cobs.last(), Some(&COBS_DELIMITER));
+            assert!(!cobs[..cobs.len() - 1].contains(&COBS_DELIMITER));
+            assert_eq!(cobs_to_slip(&cobs).unwrap(), slip);
+            assert_eq!(cobs_to_slip(&cobs[..cobs.len() - 1]).unwrap(), slip);
+        }
+    }
+
+    #[test]
+    fn malformed_frames_are_rejected() {
+        assert!(matches!(
+            cobs_to_slip(&[]),
+            Err(SlipError::InvalidCobsFrame)
+        ));
+        assert!(matches!(
+            cobs_to_slip(&[0x05, 0x01, 0x00]),
+            Err(SlipError::InvalidCobsFrame)
+        ));
+        assert!(matches!(
+            slip_to_cobs(b"unterminated"),
+            Err(SlipError::UnexpectedEndOfFrame { .. })
+        ));
+    }
+}
@@ -0,0 +1,383 @@
+use std::fmt::Write;
+use std::ops::Range;
+
+use memchr::memchr2;
+
+use crate::{crc32_update, decode_frame, CRC32_INIT, END, ESC, ESC_END, ESC_ESC};
+
+/// Number of bytes rendered on each line of a hex dump.
+const BYTES_PER_LINE: usize = 16;
+
+/// Escape sequences per decoded payload byte above which [`detect_dialect`] flags a capture.
+pub const PATHOLOGICAL_ESCAPE_RATIO: f64 = 0.5;
+
+/// Format a decoded payload as a canonical `offset  hex  |ascii|` hex dump.
+///
+/// Each line shows 16 bytes with an extra gap after the eighth, followed by the printable
+/// ASCII characters (non-printable bytes are shown as `.`). Every line ends with `\n`.
+///
+/// ```
+/// use slipspeed::debug_tools::hexdump_frame;
+///
+/// assert_eq!(
+///     hexdump_frame(b"hi\n"),
+///     "00000000  68 69 0a                                          |hi.|\n"
+/// );
+/// ```
+pub fn hexdump_frame(frame: &[u8]) -> String {
+    dump(frame, |_, byte| ascii(byte))
+}
+
+/// Format SLIP-encoded bytes as a hex dump that highlights framing bytes.
+///
+/// The layout matches [`hexdump_frame`], but the ASCII column marks [`END`] as `$`, [`ESC`]
+/// as `\` and the escape code following an `ESC` ([`ESC_END`] or [`ESC_ESC`]) as `^`, so
+/// frame boundaries and escape sequences stand out.
+///
+/// ```
+/// use slipspeed::{debug_tools::hexdump_encoded, encode_frame, END};
+///
+/// assert_eq!(
+///     hexdump_encoded(&encode_frame(&[b'a', END])),
+///     "00000000  61 db dc c0                                       |a\\^$|\n"
+/// );
+/// ```
+pub fn hexdump_encoded(encoded: &[u8]) -> String {
+    dump(encoded, |previous, byte| match (previous, byte) {
+        (Some(ESC), ESC_END | ESC_ESC) => '^',
+        (_, END) => '$',
+        (_, ESC) => '\\',
+        _ => ascii(byte),
+    })
+}
+
+/// Heuristic findings about an unknown SLIP capture, as produced by [`detect_dialect`].
+///
+/// The fields describe what the bytes look like, not what the sender intended; treat them as
+/// hints when reverse-engineering a device.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct DialectReport {
+    /// Number of non-empty frames terminated by an [`END`].
+    pub frames: usize,
+    /// Number of empty frames, i.e. [`END`] bytes at the start or directly after another.
+    pub empty_frames: usize,
+    /// Whether frames appear to be opened by an [`END`] as well as closed by one, as suggested
+    /// by RFC 1055 to flush line noise.
+    pub leading_end: bool,
+    /// Number of standard `ESC ESC_END` and `ESC ESC_ESC` sequences.
+    pub valid_escapes: usize,
+    /// Number of [`ESC`] bytes followed by anything other than a standard escape code, which
+    /// hints at a non-standard escape scheme.
+    pub invalid_escapes: usize,
+    /// Standard escape sequences per decoded payload byte of the terminated frames.
+    pub escape_ratio: f64,
+    /// Whether `escape_ratio` reaches [`PATHOLOGICAL_ESCAPE_RATIO`].
+    pub pathological_escapes: bool,
+    /// Number of bytes after the last [`END`], which belong to an unterminated frame.
+    pub trailing_bytes: usize,
+    /// Checksum every decodable frame appears to end with, if any.
+    pub checksum: Option<ChecksumGuess>,
+}
+
+/// Frame check sequence recognised by [`detect_dialect`] at the end of every frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ChecksumGuess {
+    /// CRC-16/CCITT-FALSE (polynomial 0x1021, initial value 0xFFFF) over the preceding bytes.
+    Crc16Ccitt {
+        /// Whether the checksum is stored most significant byte first.
+        big_endian: bool,
+    },
+    /// CRC-32 as used by Ethernet and zlib over the preceding bytes.
+    Crc32 {
+        /// Whether the checksum is stored most significant byte first.
+        big_endian: bool,
+    },
+}
+
+impl ChecksumGuess {
+    const CANDIDATES: [ChecksumGuess; 4] = [
+        ChecksumGuess::Crc16Ccitt { big_endian: true },
+        ChecksumGuess::Crc16Ccitt { big_endian: false },
+        ChecksumGuess::Crc32 { big_endian: false },
+        ChecksumGuess::Crc32 { big_endian: true },
+    ];
+
+    /// Number of trailing bytes the checksum occupies.
+    pub fn width(self) -> usize {
+        match self {
+            ChecksumGuess::Crc16Ccitt { .. } => 2,
+            ChecksumGuess::Crc32 { .. } => 4,
+        }
+    }
+
+    fn matches(self, frame: &[u8]) -> bool {
+        let Some(split) = frame.len().checked_sub(self.width()) else {
+            return false;
+        };
+        let (data, trailer) = frame.split_at(split);
+        let expected = match self {
+            ChecksumGuess::Crc16Ccitt { big_endian: true } => {
+                crc16_ccitt(data).to_be_bytes().to_vec()
+            }
+            ChecksumGuess::Crc16Ccitt { big_endian: false } => {
+                crc16_ccitt(data).to_le_bytes().to_vec()
+            }
+            ChecksumGuess::Crc32 { big_endian: true } => crc32(data).to_be_bytes().to_vec(),
+            ChecksumGuess::Crc32 { big_endian: false } => crc32(data).to_le_bytes().to_vec(),
+        };
+        trailer == expected
+    }
+}
+
+/// Inspect a capture of unknown origin and report which SLIP conventions it appears to use.
+///
+/// Unlike the decoders, this never fails: invalid escapes are counted rather than rejected,
+/// and bytes after the last [`END`] are only measured. A checksum is reported when every
+/// decodable non-empty frame ends with a matching CRC, so captures with only one or two
+/// frames can produce coincidental matches.
+///
+/// ```
+/// use slipspeed::debug_tools::detect_dialect;
+/// use slipspeed::{encode_frame, END};
+///
+/// let capture = [&[END][..], &encode_frame(b"one"), &[END], &encode_frame(b"two")].concat();
+/// let report = detect_dialect(&capture);
+/// assert_eq!(report.frames, 2);
+/// assert!(report.leading_end);
+/// assert_eq!(report.checksum, None);
+/// ```
+pub fn detect_dialect(bytes: &[u8]) -> DialectReport {
+    let mut frames: Vec<Range<usize>> = Vec::new();
+    let mut empty_frames = 0;
+    let mut valid_escapes = 0;
+    let mut invalid_escapes = 0;
+    let mut framed_escapes = 0;
+    let mut frame_start = 0;
+    let mut i = 0;
+
+    while let Some(rel) = memchr2(END, ESC, &bytes[i..]) {
+        let pos = i + rel;
+        if bytes[pos] == END {
+            if pos == frame_start {
+                empty_frames += 1;
+            } else {
+                frames.push(frame_start..pos + 1);
+            }
+            framed_escapes = valid_escapes;
+            frame_start = pos + 1;
+            i = pos + 1;
+            continue;
+        }
+        match bytes.get(pos + 1) {
+            Some(&(ESC_END | ESC_ESC)) => {
+                valid_escapes += 1;
+                i = pos + 2;
+            }
+            // Leave a following END in place so it still terminates the frame.
+            _ => {
+                invalid_escapes += 1;
+                i = pos + 1;
+            }
+        }
+    }
+
+    let framed_bytes = frame_start - empty_frames - frames.len();
+    let payload_bytes = framed_bytes.saturating_sub(framed_escapes);
+    let escape_ratio = if payload_bytes == 0 {
+        0.0
+    } else {
+        framed_escapes as f64 / payload_bytes as f64
+    };
+    let decoded: Vec<Vec<u8>> = frames
+        .iter()
+        .filter_map(|range| decode_frame(&bytes[range.clone()]).ok())
+        .collect();
+    let checksum = ChecksumGuess::CANDIDATES
+        .into_iter()
+        .find(|guess| !decoded.is_empty() && decoded.iter().all(|frame| guess.matches(frame)));
+
+    DialectReport {
+        frames: frames.len(),
+        empty_frames,
+        leading_end: bytes.first() == Some(&END) && 2 * empty_frames >= frames.len(),
+        valid_escapes,
+        invalid_escapes,
+        escape_ratio,
+        pathological_escapes: escape_ratio >= PATHOLOGICAL_ESCAPE_RATIO,
+        trailing_bytes: bytes.len() - frame_start,
+        checksum,
+    }
+}
+
+/// CRC-16/CCITT-FALSE, computed bitwise since it only runs on diagnostic paths.
+fn crc16_ccitt(data: &[u8]) -> u16 {
+    let mut crc = 0xFFFFu16;
+    for &byte in data {
+        crc ^= u16::from(byte) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// CRC-32 (IEEE 802.3, reflected), shared with the reader's running checksum.
+fn crc32(data: &[u8]) -> u32 {
+    !crc32_update(CRC32_INIT, data)
+}
+
+fn ascii(byte: u8) -> char {
+    if byte.is_ascii_graphic() || byte == b' ' {
+        byte as char
+    } else {
+        '.'
+    }
+}
+
+fn dump<F>(bytes: &[u8], mut symbol: F) -> String
+where
+    F: FnMut(Option<u8>, u8) -> char,
+{
+    let mut out = String::new();
+    let mut previous = None;
+    for (line, chunk) in bytes.chunks(BYTES_PER_LINE).enumerate() {
+        let mut hex = String::with_capacity(3 * BYTES_PER_LINE + 1);
+        let mut text = String::with_capacity(BYTES_PER_LINE);
+        for (i, &byte) in chunk.iter().enumerate() {
+            if i == BYTES_PER_LINE / 2 {
+                hex.push(' ');
+            }
+            let _ = write!(hex, "{byte:02x} ");
+            text.push(symbol(previous, byte));
+            // An escape code consumes the ESC, so `ESC ESC_ESC ESC_END` is not two sequences.
+            previous = match (previous, byte) {
+                (Some(ESC), ESC_END | ESC_ESC) => None,
+                _ => Some(byte),
+            };
+        }
+        let _ = writeln!(
+            out,
+            "{:08x}  {hex:<width$} |{text}|",
+            line * BYTES_PER_LINE,
+            width = 3 * BYTES_PER_LINE + 1
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_dump_spans_lines() {
+        let frame: Vec<u8> = (0x30u8..0x30 + 18).collect();
+        let expected = concat!(
+            "00000000  30 31 32 33 34 35 36 37  38 39 3a 3b 3c 3d 3e 3f  |0123456789:;<=>?|\n",
+            "00000010  40 41                                             |@A|\n",
+        );
+        assert_eq!(hexdump_frame(&frame), expected);
+        assert_eq!(hexdump_frame(&[]), "");
+    }
+
+    #[test]
+    fn encoded_dump_marks_framing_bytes() {
+        let encoded = crate::encode_frame(&[ESC, b'z', ESC_END]);
+        assert_eq!(encoded, [ESC, ESC_ESC, b'z', ESC_END, END]);
+        assert_eq!(
+            hexdump_encoded(&encoded),
+            "00000000  db dd 7a dc c0                                    |\\^z.$|\n"
+        );
+    }
+
+    #[test]
+    fn crc_check_values() {
+        assert_eq!(crc16_ccitt(b"123456789"), 0x29B1);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn dialect_of_standard_capture() {
+        let capture = [
+            crate::encode_frame(b"hello"),
+            crate::encode_frame(&[END, b'x', ESC]),
+            crate::encode_frame(b"world"),
+            b"tail".to_vec(),
+        ]
+        .concat();
+        let report = detect_dialect(&capture);
+        assert_eq!(report.frames, 3);
+        assert_eq!(report.empty_frames, 0);
+        assert!(!report.leading_end);
+        assert_eq!(report.valid_escapes, 2);
+        assert_eq!(report.invalid_escapes, 0);
+        assert_eq!(report.escape_ratio, 2.0 / 13.0);
+        assert!(!report.pathological_escapes);
+        assert_eq!(report.trailing_bytes, 4);
+        assert_eq!(report.checksum, None);
+    }
+
+    #[test]
+    fn dialect_of_leading_end_capture() {
+        let capture: Vec<u8> = [b"ab".as_slice(), b"c", b"def"]
+            .iter()
+            .flat_map(|payload| [&[END][..], &crate::encode_frame(payload)].concat())
+            .collect();
+        let report = detect_dialect(&capture);
+        assert_eq!(report.frames, 3);
+        assert_eq!(report.empty_frames, 3);
+        assert!(report.leading_end);
+        assert_eq!(report.trailing_bytes, 0);
+        assert_eq!(detect_dialect(&[]).frames, 0);
+        assert!(!detect_dialect(&[]).leading_end);
+    }
+
+    #[test]
+    fn dialect_flags_escape_heavy_and_nonstandard_escapes() {
+        let report = detect_dialect(&crate::encode_frame(&[END; 8]));
+        assert_eq!(report.valid_escapes, 8);
+        assert_eq!(report.escape_ratio, 1.0);
+        assert!(report.pathological_escapes);
+
+        let report = detect_dialect(&[b'a', ESC, 0x01, ESC, END, b'b', ESC]);
+        assert_eq!(report.invalid_escapes, 3);
+        assert_eq!(report.frames, 1);
+        assert_eq!(report.trailing_bytes, 2);
+    }
+
+    #[test]
+    fn dialect_recognises_trailing_crcs() {
+        let payloads: [&[u8]; 3] = [b"first", &[END, ESC, 0x00], b"third frame"];
+        let with_crc16: Vec<u8> = payloads
+            .iter()
+            .flat_map(|payload| {
+                let frame = [*payload, &crc16_ccitt(payload).to_be_bytes()].concat();
+                crate::encode_frame(&frame)
+            })
+            .collect();
+        assert_eq!(
+            detect_dialect(&with_crc16).checksum,
+            Some(ChecksumGuess::Crc16Ccitt { big_endian: true })
+        );
+
+        let with_crc32: Vec<u8> = payloads
+            .iter()
+            .flat_map(|payload| {
+                let frame = [*payload, &crc32(payload).to_le_bytes()].concat();
+                crate::encode_frame(&frame)
+            })
+            .collect();
+        let report = detect_dialect(&with_crc32);
+        assert_eq!(
+            report.checksum,
+            Some(ChecksumGuess::Crc32 { big_endian: false })
+        );
+        assert_eq!(report.checksum.unwrap().width(), 4);
+    }
+}
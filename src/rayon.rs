@@ -0,0 +1,81 @@
+use ::rayon::prelude::*;
+
+use crate::{encoded_frame_len, escape_runs, END};
+
+/// Encode many frames in parallel and concatenate them in order into a single buffer.
+///
+/// The encoded length of every frame is computed up front so each worker writes into its own
+/// disjoint region of the output. The result is byte-identical to concatenating
+/// [`encode_frame`](crate::encode_frame) over `frames` sequentially.
+///
+/// ```
+/// use slipspeed::{encode_frame, rayon::encode_frames_parallel};
+///
+/// let frames = [b"one".to_vec(), b"two".to_vec()];
+/// let sequential = [encode_frame(&frames[0]), encode_frame(&frames[1])].concat();
+/// assert_eq!(encode_frames_parallel(&frames), sequential);
+/// ```
+pub fn encode_frames_parallel<T>(frames: &[T]) -> Vec<u8>
+where
+    T: AsRef<[u8]> + Sync,
+{
+    let lengths: Vec<usize> = frames
+        .par_iter()
+        .map(|frame| encoded_frame_len(frame.as_ref()))
+        .collect();
+    let mut out = vec![0u8; lengths.iter().sum()];
+
+    let mut regions = Vec::with_capacity(frames.len());
+    let mut rest = &mut out[..];
+    for &len in &lengths {
+        let (region, tail) = rest.split_at_mut(len);
+        regions.push(region);
+        rest = tail;
+    }
+
+    regions
+        .into_par_iter()
+        .zip(frames.par_iter())
+        .for_each(|(region, frame)| encode_into_region(frame.as_ref(), region));
+    out
+}
+
+/// Encode `data` into `region`, which must be exactly `encoded_frame_len(data)` bytes long.
+fn encode_into_region(data: &[u8], region: &mut [u8]) {
+    let mut written = 0usize;
+    escape_runs(data, END, |run| {
+        region[written..written + run.len()].copy_from_slice(run);
+        written += run.len();
+    });
+    region[written] = END;
+    debug_assert_eq!(written + 1, region.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parallel_matches_sequential() {
+        let mut seed = 0xDEADBEEFu32;
+        let frames: Vec<Vec<u8>> = (0..2_000)
+            .map(|i| {
+                (0..i % 97)
+                    .map(|_| {
+                        seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+                        (seed >> 24) as u8
+                    })
+                    .collect()
+            })
+            .collect();
+        let sequential: Vec<u8> = frames.iter().flat_map(|f| crate::encode_frame(f)).collect();
+        assert_eq!(encode_frames_parallel(&frames), sequential);
+    }
+
+    #[test]
+    fn parallel_handles_empty_input() {
+        let frames: [&[u8]; 0] = [];
+        assert!(encode_frames_parallel(&frames).is_empty());
+        assert_eq!(encode_frames_parallel(&[&[][..]]), vec![END]);
+    }
+}
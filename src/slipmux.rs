@@ -0,0 +1,163 @@
+//! SLIPmux framing, which multiplexes IP packets, diagnostic text and CoAP messages over one
+//! serial line by the first byte of each SLIP frame.
+//!
+//! This follows draft-bormann-t2trg-slipmux. IP packets are sent unchanged and recognised by
+//! their version nibble. Diagnostic text is prefixed with [`DISPATCH_DIAGNOSTIC`]. CoAP
+//! messages are prefixed with [`DISPATCH_COAP`] and followed by a 16-bit frame check sequence
+//! as defined in RFC 1662, sent least significant byte first.
+
+use crate::{decode_frame, encode_frame, Result, SlipError};
+
+/// Dispatch byte introducing a diagnostic text frame.
+pub const DISPATCH_DIAGNOSTIC: u8 = 0x0A;
+/// Dispatch byte introducing a CoAP configuration frame.
+pub const DISPATCH_COAP: u8 = 0xA9;
+
+/// A SLIPmux frame, classified by its dispatch byte.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SlipMuxFrame {
+    /// An IPv4 or IPv6 packet, including its header.
+    Ip(Vec<u8>),
+    /// Human-readable diagnostic text.
+    Diagnostic(String),
+    /// A CoAP message, without the dispatch byte and frame check sequence.
+    Coap(Vec<u8>),
+    /// A frame with an unassigned dispatch byte, kept verbatim including that byte.
+    Unknown(Vec<u8>),
+}
+
+impl SlipMuxFrame {
+    /// Classify a decoded SLIP payload by its dispatch byte.
+    ///
+    /// Empty payloads and unassigned dispatch bytes produce [`SlipMuxFrame::Unknown`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SlipError::InvalidDiagnosticText`] for diagnostic text that is not valid UTF-8 and
+    /// [`SlipError::FrameCheckMismatch`] for a CoAP frame whose checksum does not match.
+    pub fn from_payload(mut payload: Vec<u8>) -> Result<Self> {
+        match payload.first().copied() {
+            Some(0x45..=0x4F | 0x60..=0x6F) => Ok(SlipMuxFrame::Ip(payload)),
+            Some(DISPATCH_DIAGNOSTIC) => {
+                payload.remove(0);
+                String::from_utf8(payload)
+                    .map(SlipMuxFrame::Diagnostic)
+                    .map_err(SlipError::InvalidDiagnosticText)
+            }
+            Some(DISPATCH_COAP) => {
+                if payload.len() < 3 || fcs16(&payload[1..]) != FCS16_GOOD {
+                    return Err(SlipError::FrameCheckMismatch);
+                }
+                payload.truncate(payload.len() - 2);
+                payload.remove(0);
+                Ok(SlipMuxFrame::Coap(payload))
+            }
+            _ => Ok(SlipMuxFrame::Unknown(payload)),
+        }
+    }
+
+    /// Build the SLIP payload for this frame, adding the dispatch byte and checksum.
+    ///
+    /// IP packets and unknown frames are returned unchanged, so they only round-trip if their
+    /// first byte classifies them the same way again.
+    pub fn to_payload(&self) -> Vec<u8> {
+        match self {
+            SlipMuxFrame::Ip(packet) => packet.clone(),
+            SlipMuxFrame::Diagnostic(text) => [&[DISPATCH_DIAGNOSTIC], text.as_bytes()].concat(),
+            SlipMuxFrame::Coap(message) => {
+                let fcs = !fcs16(message);
+                [&[DISPATCH_COAP], &message[..], &fcs.to_le_bytes()].concat()
+            }
+            SlipMuxFrame::Unknown(payload) => payload.clone(),
+        }
+    }
+}
+
+/// Encode a SLIPmux frame as a single SLIP frame.
+///
+/// ```
+/// use slipspeed::slipmux::{decode_slipmux, encode_slipmux, SlipMuxFrame};
+///
+/// let frame = SlipMuxFrame::Diagnostic("boot ok".into());
+/// assert_eq!(decode_slipmux(&encode_slipmux(&frame)).unwrap(), frame);
+/// ```
+pub fn encode_slipmux(frame: &SlipMuxFrame) -> Vec<u8> {
+    encode_frame(&frame.to_payload())
+}
+
+/// Decode a single SLIP frame and classify it as a SLIPmux frame.
+///
+/// The same errors as for [`decode_frame`] and [`SlipMuxFrame::from_payload`] apply.
+pub fn decode_slipmux(bytes: &[u8]) -> Result<SlipMuxFrame> {
+    SlipMuxFrame::from_payload(decode_frame(bytes)?)
+}
+
+/// Residue of [`fcs16`] over data followed by its own frame check sequence.
+const FCS16_GOOD: u16 = 0xF0B8;
+
+/// RFC 1662 FCS-16 accumulator before the final complement, computed bitwise.
+fn fcs16(data: &[u8]) -> u16 {
+    let mut fcs = 0xFFFFu16;
+    for &byte in data {
+        fcs ^= u16::from(byte);
+        for _ in 0..8 {
+            fcs = if fcs & 1 != 0 {
+                (fcs >> 1) ^ 0x8408
+            } else {
+                fcs >> 1
+            };
+        }
+    }
+    fcs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{END, ESC};
+
+    #[test]
+    fn fcs16_check_value() {
+        assert_eq!(!fcs16(b"123456789"), 0x906E);
+    }
+
+    #[test]
+    fn every_frame_type_roundtrips() {
+        let frames = [
+            SlipMuxFrame::Ip(vec![0x45, 0x00, END, ESC, 0x14]),
+            SlipMuxFrame::Ip(vec![0x60, 0, 0, 0]),
+            SlipMuxFrame::Diagnostic("temperature: 21.5 °C\n".into()),
+            SlipMuxFrame::Diagnostic(String::new()),
+            SlipMuxFrame::Coap(vec![0x40, 0x01, 0x12, 0x34, END, 0xB3]),
+            SlipMuxFrame::Coap(vec![]),
+            SlipMuxFrame::Unknown(vec![0x01, 0x02]),
+            SlipMuxFrame::Unknown(vec![]),
+        ];
+        for frame in frames {
+            let encoded = encode_slipmux(&frame);
+            assert_eq!(decode_slipmux(&encoded).unwrap(), frame);
+        }
+        assert_eq!(
+            encode_slipmux(&SlipMuxFrame::Diagnostic("hi".into())),
+            [DISPATCH_DIAGNOSTIC, b'h', b'i', END]
+        );
+    }
+
+    #[test]
+    fn malformed_frames_are_rejected() {
+        let mut coap = SlipMuxFrame::Coap(b"msg".to_vec()).to_payload();
+        *coap.last_mut().unwrap() ^= 0x01;
+        assert!(matches!(
+            decode_slipmux(&encode_frame(&coap)),
+            Err(SlipError::FrameCheckMismatch)
+        ));
+        assert!(matches!(
+            decode_slipmux(&encode_frame(&[DISPATCH_COAP, 0x00])),
+            Err(SlipError::FrameCheckMismatch)
+        ));
+        assert!(matches!(
+            decode_slipmux(&encode_frame(&[DISPATCH_DIAGNOSTIC, 0xFF])),
+            Err(SlipError::InvalidDiagnosticText(_))
+        ));
+    }
+}
@@ -0,0 +1,90 @@
+//! Canonical SLIP encode/decode pairs for verifying integrations and other language ports.
+//!
+//! Every vector pairs a raw payload with its exact RFC 1055 encoding as produced by
+//! [`encode_frame`], including the trailing [`END`].
+
+use crate::{decode_frame, encode_frame, END, ESC, ESC_END, ESC_ESC};
+
+/// A payload together with its canonical SLIP encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TestVector {
+    /// Short human-readable identifier of the vector.
+    pub name: &'static str,
+    /// Raw, unencoded payload.
+    pub payload: &'static [u8],
+    /// Canonical encoding of `payload`.
+    pub encoded: &'static [u8],
+}
+
+/// An empty payload, encoded as a lone [`END`].
+pub const EMPTY_FRAME: TestVector = TestVector {
+    name: "empty frame",
+    payload: &[],
+    encoded: &[END],
+};
+
+/// Every special byte once; only [`END`] and [`ESC`] are escaped.
+pub const ALL_SPECIAL_BYTES: TestVector = TestVector {
+    name: "all special bytes",
+    payload: &[END, ESC, ESC_END, ESC_ESC],
+    encoded: &[ESC, ESC_END, ESC, ESC_ESC, ESC_END, ESC_ESC, END],
+};
+
+/// A payload in which every byte needs escaping, doubling its size on the wire.
+pub const MAX_ESCAPE: TestVector = TestVector {
+    name: "maximum escape overhead",
+    payload: &[ESC, END, ESC, END, END, ESC],
+    encoded: &[
+        ESC, ESC_ESC, ESC, ESC_END, ESC, ESC_ESC, ESC, ESC_END, ESC, ESC_END, ESC, ESC_ESC, END,
+    ],
+};
+
+/// Ordinary payload with an [`END`] byte in the middle.
+pub const EMBEDDED_END: TestVector = TestVector {
+    name: "embedded END",
+    payload: &[b'h', b'i', END, b'!'],
+    encoded: &[b'h', b'i', ESC, ESC_END, b'!', END],
+};
+
+/// All vectors provided by this module.
+pub const ALL: &[TestVector] = &[EMPTY_FRAME, ALL_SPECIAL_BYTES, MAX_ESCAPE, EMBEDDED_END];
+
+/// Check that `payload` encodes to exactly `encoded` and that `encoded` decodes back to `payload`.
+///
+/// ```
+/// use slipspeed::test_vectors::{verify_roundtrip, ALL};
+///
+/// for vector in ALL {
+///     assert!(verify_roundtrip(vector.payload, vector.encoded), "{}", vector.name);
+/// }
+/// ```
+pub fn verify_roundtrip(payload: &[u8], encoded: &[u8]) -> bool {
+    encode_frame(payload) == encoded
+        && decode_frame(encoded).is_ok_and(|decoded| decoded == payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vectors_roundtrip() {
+        for vector in ALL {
+            assert_eq!(
+                encode_frame(vector.payload),
+                vector.encoded,
+                "{}",
+                vector.name
+            );
+            assert_eq!(
+                decode_frame(vector.encoded).unwrap(),
+                vector.payload,
+                "{}",
+                vector.name
+            );
+            assert!(verify_roundtrip(vector.payload, vector.encoded));
+        }
+        assert!(!verify_roundtrip(b"a", &[b'b', END]));
+        assert!(!verify_roundtrip(&[END], &[END, END]));
+    }
+}
@@ -0,0 +1,401 @@
+//! In-memory transports for testing protocols built on SLIP without real hardware.
+//!
+//! [`SlipPipe`] connects a [`SlipWriter`] to a [`SlipReader`] through a shared queue, so both
+//! ends of a link can be driven from one test. Wrapping the sending side in a
+//! [`FaultyTransport`] corrupts the byte stream at configurable rates to exercise error
+//! handling and resynchronisation.
+
+use std::collections::VecDeque;
+use std::io::{self, Read, Write};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::{Result, SlipReader, SlipWriter, END};
+
+type Queue = Arc<Mutex<VecDeque<u8>>>;
+
+fn lock(queue: &Queue) -> MutexGuard<'_, VecDeque<u8>> {
+    queue
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Sending half of an in-memory pipe, created by [`pipe`].
+#[derive(Debug, Clone)]
+pub struct PipeWriter(Queue);
+
+impl Write for PipeWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        lock(&self.0).extend(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Receiving half of an in-memory pipe, created by [`pipe`].
+///
+/// Reads return `Ok(0)` while the pipe is empty, which readers treat as the end of the input.
+/// Reading again after more bytes were written picks up where the previous read stopped.
+#[derive(Debug, Clone)]
+pub struct PipeReader(Queue);
+
+impl PipeReader {
+    /// Number of bytes written to the pipe but not read yet.
+    pub fn len(&self) -> usize {
+        lock(&self.0).len()
+    }
+
+    /// Check whether all written bytes have been read.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Read for PipeReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut queue = lock(&self.0);
+        let len = buf.len().min(queue.len());
+        for (slot, byte) in buf.iter_mut().zip(queue.drain(..len)) {
+            *slot = byte;
+        }
+        Ok(len)
+    }
+}
+
+/// Create a unidirectional in-memory pipe.
+///
+/// Both halves may be cloned and sent to other threads; all clones share one queue.
+pub fn pipe() -> (PipeWriter, PipeReader) {
+    let queue = Queue::default();
+    (PipeWriter(queue.clone()), PipeReader(queue))
+}
+
+/// A [`SlipWriter`] and [`SlipReader`] connected by an in-memory [`pipe`].
+///
+/// Frames are available to [`recv`](SlipPipe::recv) as soon as [`send`](SlipPipe::send)
+/// returns, so a test can alternate between both ends of a half-duplex link.
+///
+/// ```
+/// use slipspeed::testing::SlipPipe;
+///
+/// let mut pipe = SlipPipe::new();
+/// pipe.send(b"request").unwrap();
+/// assert_eq!(pipe.recv().unwrap(), Some(b"request".to_vec()));
+/// assert_eq!(pipe.recv().unwrap(), None);
+/// ```
+pub struct SlipPipe<T = PipeWriter> {
+    writer: SlipWriter<T>,
+    reader: SlipReader<PipeReader>,
+}
+
+impl SlipPipe {
+    /// Construct a pipe that delivers every byte unchanged.
+    pub fn new() -> Self {
+        Self::with_transport(|writer| writer)
+    }
+}
+
+impl Default for SlipPipe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SlipPipe<T> {
+    /// Construct a pipe whose writer sends through the transport returned by `wrap`, such as a
+    /// [`FaultyTransport`].
+    pub fn with_transport<F>(wrap: F) -> Self
+    where
+        F: FnOnce(PipeWriter) -> T,
+    {
+        let (writer, reader) = pipe();
+        Self {
+            writer: SlipWriter::new(wrap(writer)),
+            reader: SlipReader::new(reader),
+        }
+    }
+
+    /// Borrow the sending end.
+    pub fn writer(&mut self) -> &mut SlipWriter<T> {
+        &mut self.writer
+    }
+
+    /// Borrow the receiving end.
+    pub fn reader(&mut self) -> &mut SlipReader<PipeReader> {
+        &mut self.reader
+    }
+
+    /// Borrow the transport frames are sent through.
+    pub fn transport_mut(&mut self) -> &mut T {
+        self.writer.get_mut()
+    }
+
+    /// Split the pipe into its two ends.
+    pub fn into_parts(self) -> (SlipWriter<T>, SlipReader<PipeReader>) {
+        (self.writer, self.reader)
+    }
+}
+
+impl<T: Write> SlipPipe<T> {
+    /// Encode `payload` as a frame and send it through the pipe.
+    pub fn send(&mut self, payload: &[u8]) -> Result<()> {
+        self.writer.write_frame(payload)
+    }
+
+    /// Receive the next frame, or `Ok(None)` if no complete frame has been sent.
+    pub fn recv(&mut self) -> Result<Option<Vec<u8>>> {
+        self.reader.read_frame()
+    }
+}
+
+/// Number of faults a [`FaultyTransport`] has injected.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FaultStats {
+    /// Bytes with one flipped bit.
+    pub bit_flips: usize,
+    /// Bytes that were not forwarded.
+    pub dropped_bytes: usize,
+    /// [`END`] bytes that were forwarded twice.
+    pub duplicated_ends: usize,
+}
+
+impl FaultStats {
+    /// Total number of injected faults.
+    pub fn total(&self) -> usize {
+        self.bit_flips + self.dropped_bytes + self.duplicated_ends
+    }
+}
+
+/// Writer wrapper that corrupts the bytes passing through it, for robustness tests.
+///
+/// Each byte is independently dropped, has one random bit flipped, or, if it is an [`END`],
+/// is duplicated, at the configured per-byte rates. All rates default to zero. Faults are
+/// drawn from a pseudo-random generator seeded by the caller, so a failing test can be
+/// reproduced exactly.
+///
+/// ```
+/// use slipspeed::testing::{FaultyTransport, SlipPipe};
+///
+/// let mut pipe = SlipPipe::with_transport(|writer| {
+///     FaultyTransport::new(writer, 1).duplicate_end_rate(1.0)
+/// });
+/// pipe.send(b"data").unwrap();
+/// assert_eq!(pipe.recv().unwrap(), Some(b"data".to_vec()));
+/// assert_eq!(pipe.recv().unwrap(), Some(Vec::new()));
+/// assert_eq!(pipe.transport_mut().stats().duplicated_ends, 1);
+/// ```
+#[derive(Debug)]
+pub struct FaultyTransport<W> {
+    inner: W,
+    rng: u64,
+    bit_flip_rate: f64,
+    drop_rate: f64,
+    duplicate_end_rate: f64,
+    enabled: bool,
+    stats: FaultStats,
+    scratch: Vec<u8>,
+}
+
+impl<W> FaultyTransport<W> {
+    /// Wrap `inner` without injecting any faults until rates are configured.
+    pub fn new(inner: W, seed: u64) -> Self {
+        Self {
+            inner,
+            rng: seed,
+            bit_flip_rate: 0.0,
+            drop_rate: 0.0,
+            duplicate_end_rate: 0.0,
+            enabled: true,
+            stats: FaultStats::default(),
+            scratch: Vec::new(),
+        }
+    }
+
+    /// Flip one bit in this fraction of bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is not between 0 and 1.
+    pub fn bit_flip_rate(mut self, rate: f64) -> Self {
+        self.bit_flip_rate = checked_rate(rate);
+        self
+    }
+
+    /// Drop this fraction of bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is not between 0 and 1.
+    pub fn drop_rate(mut self, rate: f64) -> Self {
+        self.drop_rate = checked_rate(rate);
+        self
+    }
+
+    /// Send this fraction of [`END`] bytes twice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is not between 0 and 1.
+    pub fn duplicate_end_rate(mut self, rate: f64) -> Self {
+        self.duplicate_end_rate = checked_rate(rate);
+        self
+    }
+
+    /// Pause or resume fault injection; while disabled, bytes pass through unchanged.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Number of faults injected so far.
+    pub fn stats(&self) -> FaultStats {
+        self.stats
+    }
+
+    /// Borrow the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Borrow the underlying writer mutably.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consume the wrapper and return the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn chance(&mut self, rate: f64) -> bool {
+        rate > 0.0 && ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < rate
+    }
+
+    /// SplitMix64, which produces well-mixed output for any seed including zero.
+    fn next_u64(&mut self) -> u64 {
+        self.rng = self.rng.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.rng;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl<W: Write> Write for FaultyTransport<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !self.enabled {
+            return self.inner.write(buf);
+        }
+        let mut out = std::mem::take(&mut self.scratch);
+        out.clear();
+        for &byte in buf {
+            if self.chance(self.drop_rate) {
+                self.stats.dropped_bytes += 1;
+                continue;
+            }
+            let mut byte = byte;
+            if self.chance(self.bit_flip_rate) {
+                byte ^= 1 << (self.next_u64() % 8);
+                self.stats.bit_flips += 1;
+            }
+            out.push(byte);
+            if byte == END && self.chance(self.duplicate_end_rate) {
+                out.push(END);
+                self.stats.duplicated_ends += 1;
+            }
+        }
+        let result = self.inner.write_all(&out);
+        self.scratch = out;
+        result.map(|()| buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+fn checked_rate(rate: f64) -> f64 {
+    assert!(
+        (0.0..=1.0).contains(&rate),
+        "fault rate must be between 0 and 1"
+    );
+    rate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SlipError, ESC};
+
+    #[test]
+    fn pipe_roundtrips_interleaved_frames() {
+        let frames: Vec<Vec<u8>> = vec![
+            b"hello".to_vec(),
+            vec![END, ESC, END],
+            Vec::new(),
+            (0..=255).collect(),
+        ];
+        let mut pipe = SlipPipe::new();
+        for frame in &frames {
+            pipe.send(frame).unwrap();
+        }
+        for frame in &frames {
+            assert_eq!(pipe.recv().unwrap().as_ref(), Some(frame));
+        }
+        assert_eq!(pipe.recv().unwrap(), None);
+
+        pipe.send(b"later").unwrap();
+        assert_eq!(pipe.recv().unwrap(), Some(b"later".to_vec()));
+
+        let (mut writer, mut reader) = pipe.into_parts();
+        writer.write_frame(b"split").unwrap();
+        assert_eq!(reader.get_ref().len(), 6);
+        assert_eq!(reader.read_frame().unwrap(), Some(b"split".to_vec()));
+        assert!(reader.get_ref().is_empty());
+    }
+
+    #[test]
+    fn faulty_transport_forces_resync() {
+        let mut pipe = SlipPipe::with_transport(|writer| {
+            FaultyTransport::new(writer, 0x5EED)
+                .drop_rate(0.02)
+                .bit_flip_rate(0.02)
+        });
+        let payload = [b'a', END, b'b', ESC, b'c', END, ESC, b'd'];
+        for _ in 0..100 {
+            pipe.send(&payload).unwrap();
+        }
+        let stats = pipe.transport_mut().stats();
+        assert!(stats.dropped_bytes > 0 && stats.bit_flips > 0);
+        assert_eq!(stats.duplicated_ends, 0);
+
+        // A leading END flushes whatever corrupted frame is still open.
+        pipe.transport_mut().set_enabled(false);
+        pipe.send(b"").unwrap();
+        pipe.send(b"clean").unwrap();
+
+        let mut results = Vec::new();
+        loop {
+            match pipe.recv() {
+                Ok(Some(frame)) => results.push(Ok(frame)),
+                Ok(None) => break,
+                Err(err) => results.push(Err(err)),
+            }
+        }
+        assert!(results
+            .iter()
+            .any(|result| matches!(result, Err(SlipError::InvalidEscape(_)))));
+        assert!(results
+            .iter()
+            .any(|result| matches!(result, Ok(frame) if frame[..] == payload)));
+        assert!(matches!(results.last(), Some(Ok(frame)) if frame == b"clean"));
+    }
+
+    #[test]
+    #[should_panic(expected = "fault rate must be between 0 and 1")]
+    fn faulty_transport_rejects_invalid_rate() {
+        FaultyTransport::new(Vec::<u8>::new(), 0).drop_rate(1.5);
+    }
+}
@@ -1,14 +1,45 @@
-use bytes::BytesMut;
-use std::io::{self, Write};
-use tokio_util::codec::{Decoder, Encoder};
+use bytes::{Buf, Bytes, BytesMut};
+use std::io;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use tokio_util::codec::{Decoder, Encoder, Framed, FramedWrite};
 
-use crate::{encode_into_writer, process_byte, DecoderState, Result, SlipError};
+use crate::{
+    check_frame_closed, decode_chunk_until, escape_runs, process_byte, scan_delimiters, take_frame,
+    DecoderState, FrameRemainder, FrameSink, NoopObserver, Observer, Result, SlipError, END,
+};
 
 /// SLIP codec implementing [`tokio_util::codec::Decoder`] and [`Encoder`].
-#[derive(Default)]
-pub struct SlipCodec {
+///
+/// Decoded frames are produced as `T`, which defaults to `Vec<u8>`. See [`SharedSlipCodec`]
+/// for frames that can be cloned cheaply. Encode and decode events are reported to an
+/// [`Observer`] installed with [`with_observer`](SlipCodec::with_observer).
+pub struct SlipCodec<T = Vec<u8>, O = NoopObserver> {
     state: DecoderState,
     buffer: Vec<u8>,
+    partial_on_eof: bool,
+    item: PhantomData<fn() -> T>,
+    observer: O,
+}
+
+/// SLIP codec yielding reference-counted `Arc<[u8]>` frames.
+///
+/// Cloning an `Arc` is a reference count increment, which suits fanning one frame out to
+/// many subscribers. Converting a decoded `Vec<u8>` into an `Arc<[u8]>` copies the payload
+/// once into the shared allocation, so prefer the default `Vec<u8>` items when frames have
+/// a single consumer.
+pub type SharedSlipCodec = SlipCodec<Arc<[u8]>>;
+
+impl<T> Default for SlipCodec<T> {
+    fn default() -> Self {
+        Self {
+            state: DecoderState::default(),
+            buffer: Vec::new(),
+            partial_on_eof: false,
+            item: PhantomData,
+            observer: NoopObserver,
+        }
+    }
 }
 
 impl SlipCodec {
@@ -17,61 +48,272 @@ impl SlipCodec {
         Self::default()
     }
 
-    /// Encode a byte slice without allocating.
-    pub fn encode_slice(&mut self, item: &[u8], dst: &mut BytesMut) -> Result<()> {
-        let mut writer = BytesMutWriter(dst);
-        encode_into_writer(item.iter().copied(), &mut writer)
+    /// Construct a new [`SharedSlipCodec`] yielding `Arc<[u8]>` frames.
+    ///
+    /// This lives here rather than as `SharedSlipCodec::new`, which would make
+    /// `SlipCodec::new()` ambiguous whenever the item type is inferred.
+    pub fn shared() -> SharedSlipCodec {
+        SharedSlipCodec::default()
     }
 }
 
-impl Encoder<Vec<u8>> for SlipCodec {
-    type Error = SlipError;
+impl<T, O> SlipCodec<T, O> {
+    /// Report frames, escapes and errors to `observer`, replacing the current observer.
+    pub fn with_observer<P: Observer>(self, observer: P) -> SlipCodec<T, P> {
+        SlipCodec {
+            state: self.state,
+            buffer: self.buffer,
+            partial_on_eof: self.partial_on_eof,
+            item: PhantomData,
+            observer,
+        }
+    }
 
-    fn encode(&mut self, item: Vec<u8>, dst: &mut BytesMut) -> Result<()> {
-        let mut writer = BytesMutWriter(dst);
-        encode_into_writer(item, &mut writer)
+    /// Borrow the observer.
+    pub fn observer(&self) -> &O {
+        &self.observer
+    }
+
+    /// Borrow the observer mutably.
+    pub fn observer_mut(&mut self) -> &mut O {
+        &mut self.observer
+    }
+
+    /// Yield a truncated frame from [`Decoder::decode_eof`] instead of failing.
+    ///
+    /// By default the codec reports [`SlipError::UnexpectedEndOfFrame`] or
+    /// [`SlipError::IncompleteEscape`] when the stream closes mid-frame. With this
+    /// mode enabled the buffered payload is returned as the final item instead; a
+    /// dangling [`ESC`](crate::ESC) cannot be decoded and is dropped.
+    pub fn with_partial_on_eof(mut self, enabled: bool) -> Self {
+        self.partial_on_eof = enabled;
+        self
+    }
+
+    /// Count the complete frames that [`Decoder::decode`] could yield from `src` right now.
+    ///
+    /// The codec's buffered partial frame and escape state are taken into account, so a frame
+    /// that started in an earlier read and ends in `src` is counted. Neither the codec nor `src`
+    /// is modified.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SlipError::InvalidEscape`] if `src` contains an invalid escape sequence.
+    pub fn frames_available(&self, src: &BytesMut) -> Result<usize> {
+        let mut count = 0usize;
+        scan_delimiters(src, self.state.last_was_esc, |_| count += 1)?;
+        Ok(count)
+    }
+
+    /// Take ownership of any pending decoded bytes accumulated for the current, incomplete frame.
+    pub fn take_remainder(&mut self) -> FrameRemainder {
+        let remainder = FrameRemainder {
+            decoded: std::mem::take(&mut self.buffer),
+            escape_pending: self.state.last_was_esc,
+        };
+        self.state.last_was_esc = false;
+        remainder
     }
 }
 
-impl Decoder for SlipCodec {
-    type Item = Vec<u8>;
-    type Error = SlipError;
+impl<T, O: Observer> SlipCodec<T, O> {
+    /// Drain every complete frame from `src` in a single pass, passing each one to `f`.
+    ///
+    /// This avoids the per-item overhead of calling [`Decoder::decode`] repeatedly when many
+    /// frames arrive in one read. All of `src` is consumed; bytes after the last [`END`] stay
+    /// buffered in the codec and are completed by later calls to either method.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SlipError::InvalidEscape`] on an invalid escape sequence. Frames preceding it
+    /// have already been passed to `f`. The broken frame is discarded and `src` is advanced
+    /// just past the [`END`] that terminates it, so frames after it stay in `src` for the next
+    /// call. If that [`END`] is not in `src` yet, all of `src` is consumed.
+    ///
+    /// ```
+    /// use bytes::BytesMut;
+    /// use slipspeed::tokio_codec::SlipCodec;
+    /// use slipspeed::encode_frame;
+    ///
+    /// let mut codec = SlipCodec::new();
+    /// let mut src = BytesMut::from(&[encode_frame(b"a"), encode_frame(b"b")].concat()[..]);
+    /// let mut frames = Vec::new();
+    /// codec.decode_all(&mut src, |frame| frames.push(frame)).unwrap();
+    /// assert_eq!(frames, vec![b"a".to_vec(), b"b".to_vec()]);
+    /// ```
+    pub fn decode_all<F>(&mut self, src: &mut BytesMut, mut f: F) -> Result<()>
+    where
+        T: From<Vec<u8>>,
+        F: FnMut(T),
+    {
+        let observer = &mut self.observer;
+        let mut escapes = 0;
+        let result = decode_chunk_until(
+            &mut self.buffer,
+            &mut self.state.last_was_esc,
+            src,
+            &mut escapes,
+            take_frame(|frame| {
+                observer.on_frame(frame.len());
+                f(T::from(frame))
+            }),
+        );
+        for _ in 0..escapes {
+            self.observer.on_escape();
+        }
+        match result {
+            Ok(()) => {
+                src.clear();
+                Ok(())
+            }
+            Err((consumed, err)) => {
+                self.observer.on_error(&err);
+                self.buffer.clear();
+                // An invalid escape code that is END itself already terminated the frame.
+                let frame_end = match err {
+                    SlipError::InvalidEscape(END) => consumed,
+                    _ => memchr::memchr(END, &src[consumed..])
+                        .map_or(src.len(), |rel| consumed + rel + 1),
+                };
+                src.advance(frame_end);
+                Err(err)
+            }
+        }
+    }
 
-    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>> {
+    /// Encode a byte slice without allocating.
+    pub fn encode_slice(&mut self, item: &[u8], dst: &mut BytesMut) -> Result<()> {
+        let escapes = encode_frame_to(item, dst);
+        self.report_encoded(item.len(), escapes);
+        Ok(())
+    }
+
+    /// Notify the observer of an encoded frame.
+    fn report_encoded(&mut self, len: usize, escapes: usize) {
+        for _ in 0..escapes {
+            self.observer.on_escape();
+        }
+        self.observer.on_frame(len);
+    }
+
+    /// [`Decoder::decode`] without notifying the observer of the result.
+    fn decode_unobserved(&mut self, src: &mut BytesMut) -> Result<Option<T>>
+    where
+        T: From<Vec<u8>>,
+    {
         while !src.is_empty() {
             let byte = src.split_to(1)[0];
+            let escaped = self.state.last_was_esc;
             let completed = process_byte(&mut self.state, byte, |value| self.buffer.push(value))?;
+            if escaped {
+                self.observer.on_escape();
+            }
             if completed {
-                return Ok(Some(std::mem::take(&mut self.buffer)));
+                self.observer.on_frame(self.buffer.len());
+                return Ok(Some(T::from(std::mem::take(&mut self.buffer))));
             }
         }
         Ok(None)
     }
+}
 
-    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>> {
-        if let Some(frame) = self.decode(src)? {
-            return Ok(Some(frame));
-        }
-        if self.state.last_was_esc {
-            return Err(SlipError::IncompleteEscape);
+/// Append the encoded frame to `dst` and return the number of escapes.
+fn encode_frame_to(item: &[u8], dst: &mut BytesMut) -> usize {
+    let escapes = escape_runs(item, END, |run| dst.extend_from_slice(run));
+    dst.extend_from_slice(&[END]);
+    escapes
+}
+
+/// Encodes the frame into the write buffer; it is written out by the next flush of the sink.
+///
+/// Fails with an [`io::ErrorKind::WouldBlock`] error while the buffer holds at least
+/// [`backpressure_boundary`](FramedWrite::backpressure_boundary) bytes.
+impl<W, T, O: Observer> FrameSink for FramedWrite<W, SlipCodec<T, O>> {
+    fn send_frame(&mut self, payload: &[u8]) -> Result<()> {
+        if self.write_buffer().len() >= self.backpressure_boundary() {
+            return Err(io::Error::from(io::ErrorKind::WouldBlock).into());
         }
-        if !self.buffer.is_empty() {
-            return Err(SlipError::UnexpectedEndOfFrame);
+        let escapes = encode_frame_to(payload, self.write_buffer_mut());
+        self.encoder_mut().report_encoded(payload.len(), escapes);
+        Ok(())
+    }
+}
+
+/// Encodes the frame into the write buffer; it is written out by the next flush of the sink.
+///
+/// Fails with an [`io::ErrorKind::WouldBlock`] error while the buffer holds at least
+/// [`backpressure_boundary`](Framed::backpressure_boundary) bytes.
+impl<W, T, O: Observer> FrameSink for Framed<W, SlipCodec<T, O>> {
+    fn send_frame(&mut self, payload: &[u8]) -> Result<()> {
+        if self.write_buffer().len() >= self.backpressure_boundary() {
+            return Err(io::Error::from(io::ErrorKind::WouldBlock).into());
         }
-        Ok(None)
+        let escapes = encode_frame_to(payload, self.write_buffer_mut());
+        self.codec_mut().report_encoded(payload.len(), escapes);
+        Ok(())
     }
 }
 
-struct BytesMutWriter<'a>(&'a mut BytesMut);
+impl<T, O: Observer> Encoder<Vec<u8>> for SlipCodec<T, O> {
+    type Error = SlipError;
 
-impl<'a> Write for BytesMutWriter<'a> {
-    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.0.extend_from_slice(buf);
-        Ok(buf.len())
+    fn encode(&mut self, item: Vec<u8>, dst: &mut BytesMut) -> Result<()> {
+        self.encode_slice(&item, dst)
     }
+}
 
-    fn flush(&mut self) -> io::Result<()> {
-        Ok(())
+/// Encode borrowed slices without first copying them into a `Vec`.
+///
+/// With [`Framed`], `framed.send(&payload[..])` borrows `payload`
+/// only until the returned future completes: the frame is escaped into the write buffer when
+/// the sink accepts the item, so the slice need not outlive the send.
+///
+/// Because the codec encodes several item types, item-agnostic sink calls such as
+/// `SinkExt::flush` need the item type spelled out, e.g. `SinkExt::<&[u8]>::flush(&mut framed)`.
+impl<'a, T, O: Observer> Encoder<&'a [u8]> for SlipCodec<T, O> {
+    type Error = SlipError;
+
+    fn encode(&mut self, item: &'a [u8], dst: &mut BytesMut) -> Result<()> {
+        self.encode_slice(item, dst)
+    }
+}
+
+impl<T, O: Observer> Encoder<Bytes> for SlipCodec<T, O> {
+    type Error = SlipError;
+
+    fn encode(&mut self, item: Bytes, dst: &mut BytesMut) -> Result<()> {
+        self.encode_slice(&item, dst)
+    }
+}
+
+impl<T: From<Vec<u8>>, O: Observer> Decoder for SlipCodec<T, O> {
+    type Item = T;
+    type Error = SlipError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>> {
+        let result = self.decode_unobserved(src);
+        if let Err(err) = &result {
+            self.observer.on_error(err);
+        }
+        result
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>> {
+        if let Some(frame) = self.decode(src)? {
+            return Ok(Some(frame));
+        }
+        if self.partial_on_eof {
+            let remainder = self.take_remainder();
+            if remainder.is_empty() {
+                return Ok(None);
+            }
+            return Ok(Some(T::from(remainder.decoded)));
+        }
+        if let Err(err) = check_frame_closed(self.buffer.len(), self.state.last_was_esc) {
+            self.observer.on_error(&err);
+            return Err(err);
+        }
+        Ok(None)
     }
 }
 
@@ -119,7 +361,10 @@ mod tests {
         let mut src = BytesMut::from(&frame[..]);
         assert!(codec.decode(&mut src).unwrap().is_none());
         let err = codec.decode_eof(&mut src).unwrap_err();
-        assert!(matches!(err, SlipError::UnexpectedEndOfFrame));
+        assert!(matches!(
+            err,
+            SlipError::UnexpectedEndOfFrame { buffered: 6 }
+        ));
     }
 
     #[test]
@@ -128,6 +373,228 @@ mod tests {
         let mut src = BytesMut::from(&[crate::ESC][..]);
         assert!(codec.decode(&mut src).unwrap().is_none());
         let err = codec.decode_eof(&mut src).unwrap_err();
-        assert!(matches!(err, SlipError::IncompleteEscape));
+        assert!(matches!(err, SlipError::IncompleteEscape { buffered: 0 }));
+    }
+
+    #[test]
+    fn frames_available_accounts_for_partial_state() {
+        let mut codec = SlipCodec::new();
+        let mut src = BytesMut::from(&[b'a', crate::ESC][..]);
+        assert!(codec.decode(&mut src).unwrap().is_none());
+        assert_eq!(codec.frames_available(&src).unwrap(), 0);
+
+        src.extend_from_slice(&[crate::ESC_END, crate::END]);
+        src.extend_from_slice(&crate::encode_frame(&[crate::END]));
+        src.extend_from_slice(&crate::encode_frame(b"two"));
+        src.extend_from_slice(b"partial");
+        assert_eq!(codec.frames_available(&src).unwrap(), 3);
+        assert_eq!(src.len(), 2 + 3 + 4 + 7);
+
+        assert_eq!(
+            codec.decode(&mut src).unwrap().unwrap(),
+            vec![b'a', crate::END]
+        );
+        assert_eq!(codec.frames_available(&src).unwrap(), 2);
+    }
+
+    #[test]
+    fn frames_available_rejects_invalid_escape() {
+        let codec = SlipCodec::new();
+        let src = BytesMut::from(&[crate::ESC, 0x01, crate::END][..]);
+        assert!(matches!(
+            codec.frames_available(&src),
+            Err(SlipError::InvalidEscape(0x01))
+        ));
+    }
+
+    #[test]
+    fn decode_partial_on_eof_yields_truncated_frame() {
+        let mut frame = crate::encode_frame(b"broken");
+        frame.pop();
+        let mut codec = SlipCodec::new().with_partial_on_eof(true);
+        let mut src = BytesMut::from(&frame[..]);
+        assert!(codec.decode(&mut src).unwrap().is_none());
+        assert_eq!(codec.decode_eof(&mut src).unwrap().unwrap(), b"broken");
+        assert!(codec.decode_eof(&mut src).unwrap().is_none());
+    }
+
+    #[test]
+    fn take_remainder_after_truncated_frame() {
+        let mut codec = SlipCodec::new();
+        let mut src = BytesMut::from(&[b'a', b'b', crate::ESC][..]);
+        assert!(codec.decode(&mut src).unwrap().is_none());
+        let remainder = codec.take_remainder();
+        assert_eq!(remainder.decoded, b"ab");
+        assert!(remainder.escape_pending);
+        assert!(codec.decode_eof(&mut src).unwrap().is_none());
+    }
+
+    #[test]
+    fn decode_all_drains_frames_and_keeps_partial() {
+        let mut codec = SlipCodec::new();
+        let mut input = [
+            crate::encode_frame(b"one"),
+            crate::encode_frame(&[crate::END]),
+            crate::encode_frame(b""),
+        ]
+        .concat();
+        input.extend_from_slice(&[b't', crate::ESC]);
+        let mut src = BytesMut::from(&input[..]);
+
+        let mut frames = Vec::new();
+        codec
+            .decode_all(&mut src, |frame| frames.push(frame))
+            .unwrap();
+        assert_eq!(frames, vec![b"one".to_vec(), vec![crate::END], Vec::new()]);
+        assert!(src.is_empty());
+
+        src.extend_from_slice(&[crate::ESC_ESC, crate::END]);
+        assert_eq!(
+            codec.decode(&mut src).unwrap(),
+            Some(vec![b't', crate::ESC])
+        );
+    }
+
+    #[test]
+    fn decode_all_skips_only_the_broken_frame() {
+        let mut codec = SlipCodec::new();
+        let input = [
+            crate::encode_frame(b"one"),
+            vec![b'x', crate::ESC, 0x42, b'y', crate::END],
+            crate::encode_frame(b"two"),
+        ]
+        .concat();
+        let mut src = BytesMut::from(&input[..]);
+        let mut frames = Vec::new();
+        let err = codec
+            .decode_all(&mut src, |frame| frames.push(frame))
+            .unwrap_err();
+        assert!(matches!(err, SlipError::InvalidEscape(0x42)));
+        assert_eq!(frames, vec![b"one".to_vec()]);
+        assert_eq!(&src[..], crate::encode_frame(b"two"));
+        codec
+            .decode_all(&mut src, |frame| frames.push(frame))
+            .unwrap();
+        assert_eq!(frames, vec![b"one".to_vec(), b"two".to_vec()]);
+
+        // An END in place of the escape code ends the broken frame itself.
+        let input = [
+            vec![b'x', crate::ESC, crate::END],
+            crate::encode_frame(b"three"),
+        ]
+        .concat();
+        let mut src = BytesMut::from(&input[..]);
+        let mut frames = Vec::new();
+        let err = codec
+            .decode_all(&mut src, |frame| frames.push(frame))
+            .unwrap_err();
+        assert!(matches!(err, SlipError::InvalidEscape(crate::END)));
+        codec
+            .decode_all(&mut src, |frame| frames.push(frame))
+            .unwrap();
+        assert_eq!(frames, vec![b"three".to_vec()]);
+    }
+
+    #[test]
+    fn shared_codec_yields_arc_frames() {
+        let mut codec = SlipCodec::shared();
+        let mut src = BytesMut::from(&crate::encode_frame(&[b'a', crate::END])[..]);
+        let frame = codec.decode(&mut src).unwrap().unwrap();
+        assert_eq!(&frame[..], &[b'a', crate::END]);
+
+        let subscriber = Arc::clone(&frame);
+        assert_eq!(Arc::strong_count(&frame), 2);
+        assert!(Arc::ptr_eq(&frame, &subscriber));
+    }
+
+    #[test]
+    fn encode_borrowed_and_bytes_items() {
+        let mut codec = SlipCodec::new();
+        let mut dst = BytesMut::new();
+        let payload = [b'a', crate::END];
+        Encoder::<&[u8]>::encode(&mut codec, &payload, &mut dst).unwrap();
+        Encoder::<Bytes>::encode(&mut codec, Bytes::from_static(b"b"), &mut dst).unwrap();
+        let expected = [crate::encode_frame(&payload), crate::encode_frame(b"b")].concat();
+        assert_eq!(&dst[..], expected);
+    }
+
+    #[derive(Default)]
+    struct Events {
+        frames: Vec<usize>,
+        escapes: usize,
+        errors: usize,
+    }
+
+    impl Observer for Events {
+        fn on_frame(&mut self, len: usize) {
+            self.frames.push(len);
+        }
+
+        fn on_error(&mut self, _error: &SlipError) {
+            self.errors += 1;
+        }
+
+        fn on_escape(&mut self) {
+            self.escapes += 1;
+        }
+    }
+
+    #[test]
+    fn observer_sees_codec_events() {
+        use crate::ESC;
+
+        let mut codec = SlipCodec::<Vec<u8>>::default().with_observer(Events::default());
+        let mut dst = BytesMut::new();
+        codec.encode_slice(&[crate::END, b'a'], &mut dst).unwrap();
+        assert_eq!(codec.observer().frames, vec![2]);
+        assert_eq!(codec.observer().escapes, 1);
+
+        // Escapes split across calls count once, rejected ones not at all.
+        dst.extend_from_slice(&[b'b', ESC]);
+        codec.decode(&mut dst).unwrap().unwrap();
+        assert!(codec.decode(&mut dst).unwrap().is_none());
+        let mut src = BytesMut::from(&[crate::ESC_ESC, END, b'c', ESC, 0x01, END][..]);
+        let mut frames = Vec::new();
+        assert!(codec
+            .decode_all(&mut src, |frame| frames.push(frame))
+            .is_err());
+        assert_eq!(frames, vec![vec![b'b', ESC]]);
+
+        let events = codec.observer_mut();
+        assert_eq!(events.frames, vec![2, 2, 2]);
+        assert_eq!(events.escapes, 3);
+        assert_eq!(events.errors, 1);
+    }
+
+    #[test]
+    fn framed_sinks_share_frame_sink_with_writers() {
+        use crate::{send_all, SlipWriter, ESC};
+
+        let mut write_half = FramedWrite::new(Vec::<u8>::new(), SlipCodec::<Vec<u8>>::new());
+        let mut framed = Framed::new(
+            std::io::Cursor::new(Vec::new()),
+            SlipCodec::<Vec<u8>>::new(),
+        );
+        let mut plain = SlipWriter::new(Vec::new());
+        for sink in [
+            &mut write_half as &mut dyn FrameSink,
+            &mut framed,
+            &mut plain,
+        ] {
+            send_all(sink, [&b"a"[..], &[END, ESC]]).unwrap();
+        }
+        let expected = plain.into_inner();
+        assert_eq!(&write_half.write_buffer()[..], expected);
+        assert_eq!(&framed.write_buffer()[..], expected);
+
+        for sink in [&mut write_half as &mut dyn FrameSink, &mut framed] {
+            sink.send_frame(&[0; 8 * 1024]).unwrap();
+            assert!(matches!(
+                sink.send_frame(b"x"),
+                Err(SlipError::Io(err)) if err.kind() == io::ErrorKind::WouldBlock
+            ));
+        }
+        write_half.set_backpressure_boundary(16 * 1024);
+        write_half.send_frame(b"x").unwrap();
     }
 }